@@ -0,0 +1,133 @@
+//! actix-web integration
+//!
+//! The [`SupabaseUser`] and [`SupabaseClaims`] extractors read the request's
+//! `Authorization: Bearer` header, validate the token with the [`AuthClient`] registered
+//! as app data, and store the outcome in the request extensions so other extractors of
+//! the same request reuse it. Requests without a valid token are rejected with
+//! `401 Unauthorized`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use actix_web::{get, web, App};
+//! use supabase_auth_redux::actix::SupabaseUser;
+//! use supabase_auth_redux::AuthClient;
+//!
+//! #[get("/me")]
+//! async fn me(SupabaseUser(user): SupabaseUser) -> String {
+//!     format!("Hello {}", user.id)
+//! }
+//!
+//! # fn example() -> Result<(), supabase_auth_redux::AuthError> {
+//! let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+//! let app = App::new()
+//!     .app_data(web::Data::new(client))
+//!     .service(me);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::{header, StatusCode};
+use actix_web::{web, FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError};
+use tracing::{debug, error};
+
+use crate::models::claims::Claims;
+use crate::{AuthClient, AuthError, User};
+
+/// The user owning the request's bearer token, looked up with
+/// [`AuthClient::get_user_by_token`]
+///
+/// Signed out sessions are rejected immediately, at the cost of a request to the auth
+/// server per request.
+#[derive(Debug, Clone)]
+pub struct SupabaseUser(pub User);
+
+/// Claims of the request's bearer token, verified locally with
+/// [`AuthClient::verify_access_token`]
+///
+/// Requires a [`JwtVerifier`](crate::jwt::JwtVerifier) configured on the client.
+#[derive(Debug, Clone)]
+pub struct SupabaseClaims(pub Claims);
+
+impl FromRequest for SupabaseUser {
+    type Error = AuthError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            if let Some(user) = req.extensions().get::<SupabaseUser>() {
+                return Ok(user.clone());
+            }
+            let client = auth_client(&req)?;
+            let token = bearer_token(&req)?;
+            let user = SupabaseUser(client.get_user_by_token(&token).await.inspect_err(|e| {
+                debug!("rejected bearer token: {}", e);
+            })?);
+            req.extensions_mut().insert(user.clone());
+            Ok(user)
+        })
+    }
+}
+
+impl FromRequest for SupabaseClaims {
+    type Error = AuthError;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        std::future::ready(verify_claims(req))
+    }
+}
+
+fn verify_claims(req: &HttpRequest) -> Result<SupabaseClaims, AuthError> {
+    if let Some(claims) = req.extensions().get::<SupabaseClaims>() {
+        return Ok(claims.clone());
+    }
+    let client = auth_client(req)?;
+    let token = bearer_token(req)?;
+    let claims = SupabaseClaims(client.verify_access_token(&token).inspect_err(|e| {
+        debug!("rejected bearer token: {}", e);
+    })?);
+    req.extensions_mut().insert(claims.clone());
+    Ok(claims)
+}
+
+/// Returns the client registered with `App::app_data`, as `web::Data` or directly
+fn auth_client(req: &HttpRequest) -> Result<AuthClient, AuthError> {
+    if let Some(client) = req.app_data::<web::Data<AuthClient>>() {
+        return Ok(client.get_ref().clone());
+    }
+    req.app_data::<AuthClient>().cloned().ok_or_else(|| {
+        error!("no AuthClient registered as app data");
+        AuthError::Internal
+    })
+}
+
+/// Returns the token of the request's `Authorization: Bearer` header
+fn bearer_token(req: &HttpRequest) -> Result<String, AuthError> {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .and_then(|(scheme, token)| {
+            let token = token.trim();
+            (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then(|| token.to_string())
+        });
+    token.ok_or(AuthError::NotAuthorized(None))
+}
+
+/// Responds with the error's status and its JSON serialization
+impl ResponseError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self)
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::admin::{AuthStats, UserListFilter};
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+/// Page size used when walking through the audit log
+const AUDIT_PAGE_SIZE: u32 = 100;
+
+/// Audit log actions recorded when a user signs in
+const SIGN_IN_ACTIONS: &[&str] = &["login"];
+
+/// Audit log actions showing a user holds a live session
+const SESSION_ACTIONS: &[&str] = &["login", "token_refreshed"];
+
+#[derive(Debug, Deserialize)]
+struct AuditLogEntry {
+    payload: AuditLogPayload,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AuditLogPayload {
+    action: String,
+    actor_id: String,
+}
+
+impl AuthClient {
+    /// Aggregates signups, confirmations, sign-ins and active sessions over a time window
+    ///
+    /// Signups and confirmations are counted from the admin user listing, sign-ins and
+    /// active sessions from the audit log. GoTrue does not list sessions, so a session
+    /// counts as active when its user signed in or refreshed a token during the window.
+    /// The operation pages through every user and every audit log entry of the window, so
+    /// it is meant for periodic dashboards rather than request paths.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - How far back from now to aggregate
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if `window` is negative.
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::Http` if an API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let stats = admin_client
+    ///     .admin_auth_stats(time::Duration::days(1))
+    ///     .await?;
+    /// println!(
+    ///     "{} signups, {} sign-ins, {} active sessions",
+    ///     stats.signups, stats.sign_ins, stats.active_sessions
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_auth_stats(&self, window: Duration) -> Result<AuthStats, AuthError> {
+        self.observe("admin_auth_stats", None, async {
+            if window.is_negative() {
+                error!("negative window");
+                return Err(AuthError::InvalidParameters(None));
+            }
+            if self.supabase_service_role_key.is_none() {
+                return Err(AuthError::ServiceRoleKeyRequired);
+            }
+
+            let until = OffsetDateTime::now_utc();
+            let since = until - window;
+
+            let users = self
+                .admin_list_users_matching(&UserListFilter::new())
+                .await?;
+            let signups = users
+                .iter()
+                .filter(|user| user.created_at.is_some_and(|at| at >= since))
+                .count() as u64;
+            let confirmations = users
+                .iter()
+                .filter(|user| {
+                    user.email_confirmed_at
+                        .into_iter()
+                        .chain(user.phone_confirmed_at)
+                        .any(|at| at >= since)
+                })
+                .count() as u64;
+
+            let mut sign_ins = 0;
+            let mut active_users = HashSet::new();
+            let mut page = 1;
+            loop {
+                let entries = self.admin_audit_log_page(page, AUDIT_PAGE_SIZE).await?;
+                let page_len = entries.len();
+
+                // Entries are listed newest first, so older pages fall outside the window
+                let reached_older_entries = entries.last().is_some_and(|e| e.created_at < since);
+
+                for entry in entries.into_iter().filter(|e| e.created_at >= since) {
+                    let action = entry.payload.action.as_str();
+                    if SIGN_IN_ACTIONS.contains(&action) {
+                        sign_ins += 1;
+                    }
+                    if SESSION_ACTIONS.contains(&action) && !entry.payload.actor_id.is_empty() {
+                        active_users.insert(entry.payload.actor_id);
+                    }
+                }
+
+                if page_len < AUDIT_PAGE_SIZE as usize || reached_older_entries {
+                    break;
+                }
+                page += 1;
+            }
+
+            let stats = AuthStats {
+                since,
+                until,
+                signups,
+                confirmations,
+                sign_ins,
+                active_sessions: active_users.len() as u64,
+            };
+            info!(?stats, "computed auth stats");
+            Ok(stats)
+        })
+        .await
+    }
+
+    async fn admin_audit_log_page(
+        &self,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<AuditLogEntry>, AuthError> {
+        let service_role_key = self
+            .supabase_service_role_key
+            .as_ref()
+            .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+        let resp = match self
+            .send_with_failover(|api_url| {
+                self.http_client
+                    .get(format!("{}/admin/audit", api_url))
+                    .query(&[("page", page), ("per_page", per_page)])
+                    .bearer_auth(service_role_key)
+                    .header("apiKey", service_role_key)
+            })
+            .instrument(trace_span!("gotrue admin audit log"))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+
+        let resp_code_result = handle_response(&resp).await;
+        let resp_text = match resp.text().await {
+            Ok(resp_text) => resp_text,
+            Err(e) => {
+                log::error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        debug!("resp_text: {}", self.redaction.response(&resp_text));
+        resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+        match serde_json::from_str::<Vec<AuditLogEntry>>(&resp_text) {
+            Ok(entries) => Ok(entries),
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+}
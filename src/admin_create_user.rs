@@ -0,0 +1,145 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::{ApiError, AuthError};
+use crate::models::admin::{AdminCreateUserParams, PasswordHashFormat};
+use crate::models::user::UserSchema;
+use crate::util::{error_with_body, handle_response, mask_identifier};
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Creates a user through the admin API
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    /// Unlike `signup`, no session is created for the new user and confirmation emails
+    /// can be skipped with `email_confirm` / `phone_confirm`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The attributes of the user to create
+    ///
+    /// # Returns
+    ///
+    /// Returns the newly created `UserSchema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if both a password and a password hash are
+    /// given, the password hash is not in a supported [`PasswordHashFormat`], or the server
+    /// rejects the parameters.
+    /// Returns `AuthError::WeakPassword` if the password violates the configured
+    /// [`PasswordPolicy`](crate::password_policy::PasswordPolicy).
+    /// Returns `AuthError::UserAlreadyExists` if the email or phone number is already
+    /// registered.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::admin::AdminCreateUserParams;
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let admin_client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .service_role_key("your-service-role-key")
+    ///     .build()?;
+    ///
+    /// let user = admin_client
+    ///     .admin_create_user(AdminCreateUserParams {
+    ///         email: Some("migrated@example.com".to_string()),
+    ///         password: Some("secure_password".to_string()),
+    ///         email_confirm: true,
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    ///
+    /// println!("Created user: {}", user.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_create_user(
+        &self,
+        mut params: AdminCreateUserParams,
+    ) -> Result<UserSchema, AuthError> {
+        params.email = params.email.map(|email| self.normalize_email(email));
+        self.observe(
+            "admin_create_user",
+            params
+                .email
+                .as_deref()
+                .or(params.phone.as_deref())
+                .map(mask_identifier),
+            async {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_ref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+                if let Some(password) = &params.password {
+                    self.check_password_policy(password)?;
+                }
+                validate_password_hash(&params)?;
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        self.http_client
+                            .post(format!("{}/admin/users", api_url))
+                            .json(&params)
+                            .bearer_auth(service_role_key)
+                            .header("apiKey", service_role_key)
+                    })
+                    .instrument(trace_span!("gotrue admin create user"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = handle_response(&resp).await;
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!("resp_text: {}", self.redaction.response(&resp_text));
+                resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+                let user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                    Ok(user) => user,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Internal);
+                    }
+                };
+                info!(user_id = user.id.to_string(), "created user");
+
+                Ok(user)
+            },
+        )
+        .await
+    }
+}
+
+/// Rejects password hashes GoTrue would not accept before the request is sent
+fn validate_password_hash(params: &AdminCreateUserParams) -> Result<(), AuthError> {
+    let Some(password_hash) = &params.password_hash else {
+        return Ok(());
+    };
+    let message = if params.password.is_some() {
+        "only one of password and password_hash may be given"
+    } else if PasswordHashFormat::detect(password_hash).is_none() {
+        "password_hash is not a bcrypt, argon2 or firebase scrypt hash"
+    } else {
+        return Ok(());
+    };
+    error!("{}", message);
+    Err(AuthError::InvalidParameters(Some(Box::new(
+        ApiError::validation(message.to_string()),
+    ))))
+}
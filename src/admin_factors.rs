@@ -0,0 +1,172 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::user::MFAFactorSchema;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Lists the MFA factors of a user through the admin API
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::NotFound` if the user does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// for factor in admin_client.admin_list_factors(user_id).await? {
+    ///     println!("{:?} ({:?})", factor.friendly_name, factor.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_list_factors(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MFAFactorSchema>, AuthError> {
+        self.observe("admin_list_factors", Some(user_id.to_string()), async {
+            let service_role_key = self
+                .supabase_service_role_key
+                .as_ref()
+                .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .get(format!("{}/admin/users/{}/factors", api_url, user_id))
+                        .bearer_auth(service_role_key)
+                        .header("apiKey", service_role_key)
+                })
+                .instrument(trace_span!("gotrue admin list factors"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = match resp.status() {
+                reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                _ => handle_response(&resp).await,
+            };
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            match serde_json::from_str::<Vec<MFAFactorSchema>>(&resp_text) {
+                Ok(factors) => Ok(factors),
+                Err(e) => {
+                    error!("{}", e);
+                    Err(AuthError::Internal)
+                }
+            }
+        })
+        .await
+    }
+
+    /// Deletes an MFA factor of a user through the admin API
+    ///
+    /// Lets support staff reset a lost authenticator: once its last verified factor is
+    /// deleted, the user signs in without a second factor and can enroll a new one.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user owning the factor
+    /// * `factor_id` - The UUID of the factor to delete
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::NotFound` if the user or factor does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// for factor in admin_client.admin_list_factors(user_id).await? {
+    ///     if let Some(factor_id) = factor.id {
+    ///         admin_client.admin_delete_factor(user_id, factor_id).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_delete_factor(
+        &self,
+        user_id: Uuid,
+        factor_id: Uuid,
+    ) -> Result<(), AuthError> {
+        self.observe("admin_delete_factor", Some(user_id.to_string()), async {
+            let service_role_key = self
+                .supabase_service_role_key
+                .as_ref()
+                .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .delete(format!(
+                            "{}/admin/users/{}/factors/{}",
+                            api_url, user_id, factor_id
+                        ))
+                        .bearer_auth(service_role_key)
+                        .header("apiKey", service_role_key)
+                })
+                .instrument(trace_span!("gotrue admin delete factor"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = match resp.status() {
+                reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                _ => handle_response(&resp).await,
+            };
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            info!(factor_id = factor_id.to_string(), "deleted factor");
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,197 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::admin::{GenerateLinkParams, GenerateLinkResponse, LinkDelivery, LinkType};
+use crate::models::otp::{OtpOptions, RecoveryOptions};
+use crate::util::{error_with_body, handle_response, mask_identifier};
+use crate::{AuthClient, IdType};
+
+impl AuthClient {
+    /// Generates an invite, magic link, recovery, signup or email change link through the
+    /// admin API
+    ///
+    /// No email is sent; deliver the returned link or code through your own email provider.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The kind of link, the user's email address and link options
+    ///
+    /// # Returns
+    ///
+    /// Returns the action link, email OTP and hashed token together with the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the server rejects the parameters (e.g. a
+    /// signup link without password).
+    /// Returns `AuthError::NotFound` if the link requires an existing user and there is none.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::admin::{GenerateLinkParams, LinkType};
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let link = admin_client
+    ///     .admin_generate_link(
+    ///         GenerateLinkParams::new(LinkType::Invite, "new-hire@example.com")
+    ///             .redirect_to("https://app.example.com/welcome"),
+    ///     )
+    ///     .await?;
+    /// // Send `link.action_link` with your own email provider
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_generate_link(
+        &self,
+        mut params: GenerateLinkParams,
+    ) -> Result<GenerateLinkResponse, AuthError> {
+        params.email = self.normalize_email(params.email);
+        params.new_email = params.new_email.map(|email| self.normalize_email(email));
+        if params.redirect_to.is_none() {
+            params.redirect_to = self.default_redirect_to.clone();
+        }
+        self.observe(
+            "admin_generate_link",
+            Some(mask_identifier(&params.email)),
+            async {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_ref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+                if params.email.is_empty() {
+                    error!("empty email");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        self.http_client
+                            .post(format!("{}/admin/generate_link", api_url))
+                            .json(&params)
+                            .bearer_auth(service_role_key)
+                            .header("apiKey", service_role_key)
+                    })
+                    .instrument(trace_span!("gotrue admin generate link"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = handle_response(&resp).await;
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                resp_code_result.map_err(|e| {
+                    debug!("resp_text: {}", self.redaction.response(&resp_text));
+                    error_with_body(e, &resp_text)
+                })?;
+
+                let link = match serde_json::from_str::<GenerateLinkResponse>(&resp_text) {
+                    Ok(link) => link,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Internal);
+                    }
+                };
+                info!(
+                    user_id = link.user.id.to_string(),
+                    link_type = ?params.link_type,
+                    "generated link"
+                );
+
+                Ok(link)
+            },
+        )
+        .await
+    }
+    /// Sends an invite, magic link or recovery link, or returns it for custom delivery
+    ///
+    /// With [`LinkDelivery::Email`] GoTrue emails the link like
+    /// [`AuthClient::invite_user_by_email`], [`AuthClient::signin_with_otp`] (without
+    /// creating users) and [`AuthClient::reset_password_for_email`] do. With
+    /// [`LinkDelivery::Return`] no email is sent and the link is generated like
+    /// [`AuthClient::admin_generate_link`], so a custom mail pipeline can deliver it. This
+    /// lets one code path switch between both, e.g. per tenant.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Returns
+    ///
+    /// Returns the generated link for [`LinkDelivery::Return`] and `None` once GoTrue sent
+    /// the email.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if GoTrue is asked to email a signup or email
+    /// change link, which it only sends from the signup and user update flows.
+    /// Otherwise fails like the operation delivering the link.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::admin::{GenerateLinkParams, LinkDelivery, LinkType};
+    ///
+    /// # async fn example(admin_client: AuthClient, custom_mailer: bool) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let delivery = if custom_mailer { LinkDelivery::Return } else { LinkDelivery::Email };
+    /// let params = GenerateLinkParams::new(LinkType::Recovery, "user@example.com");
+    /// if let Some(link) = admin_client.admin_send_link(params, delivery).await? {
+    ///     // Send `link.action_link` or `link.email_otp` with your own email provider
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn admin_send_link(
+        &self,
+        params: GenerateLinkParams,
+        delivery: LinkDelivery,
+    ) -> Result<Option<GenerateLinkResponse>, AuthError> {
+        if self.supabase_service_role_key.is_none() {
+            return Err(AuthError::ServiceRoleKeyRequired);
+        }
+        if delivery == LinkDelivery::Return {
+            return self.admin_generate_link(params).await.map(Some);
+        }
+
+        let redirect_to = params.redirect_to.as_deref();
+        match params.link_type {
+            LinkType::Invite => {
+                self.invite_user_by_email(&params.email, params.data, redirect_to)
+                    .await?;
+            }
+            LinkType::Magiclink => {
+                let mut options = OtpOptions::new().should_create_user(false);
+                options.email_redirect_to = params.redirect_to.clone();
+                options.data = params.data;
+                self.signin_with_otp(IdType::Email(params.email), options)
+                    .await?;
+            }
+            LinkType::Recovery => {
+                let mut options = RecoveryOptions::new();
+                options.redirect_to = params.redirect_to.clone();
+                self.reset_password_for_email(&params.email, options)
+                    .await?;
+            }
+            LinkType::Signup | LinkType::EmailChangeCurrent | LinkType::EmailChangeNew => {
+                error!(link_type = ?params.link_type, "link type cannot be emailed by the admin api");
+                return Err(AuthError::InvalidParameters(None));
+            }
+        }
+        Ok(None)
+    }
+}
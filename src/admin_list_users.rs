@@ -0,0 +1,376 @@
+use reqwest::header::LINK;
+use time::OffsetDateTime;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::admin::{UserList, UserListFilter};
+use crate::models::user::UserSchema;
+use crate::util::{error_with_body, handle_response, mask_identifier};
+use crate::AuthClient;
+
+/// Page size used when walking through all users
+const LIST_PAGE_SIZE: u32 = 100;
+
+impl AuthClient {
+    /// Lists users through the admin API
+    ///
+    /// The returned page carries the total number of users and the number of the next
+    /// page, if any, so callers can page through all users.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The page to return, starting at 1
+    /// * `per_page` - The number of users per page
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let admin_client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .service_role_key("your-service-role-key")
+    ///     .build()?;
+    ///
+    /// let mut page = Some(1);
+    /// while let Some(current) = page {
+    ///     let user_list = admin_client.admin_list_users(current, 50).await?;
+    ///     for user in &user_list.users {
+    ///         println!("{}: {:?}", user.id, user.email);
+    ///     }
+    ///     page = user_list.next_page;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_list_users(&self, page: u32, per_page: u32) -> Result<UserList, AuthError> {
+        self.observe("admin_list_users", None, async {
+            self.admin_list_users_page(page, per_page, None).await
+        })
+        .await
+    }
+
+    /// Lists all users matching `filter` through the admin API
+    ///
+    /// This method pages through the user listing and collects every user matching the
+    /// filter, see [`UserListFilter`] for which parts are evaluated by the server.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::Http` if one of the API requests fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::admin::UserListFilter;
+    /// # use time::{Duration, OffsetDateTime};
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let unconfirmed_this_week = admin_client
+    ///     .admin_list_users_filtered(
+    ///         &UserListFilter::new()
+    ///             .created_after(OffsetDateTime::now_utc() - Duration::weeks(1))
+    ///             .email_confirmed(false),
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("{} users to nudge", unconfirmed_this_week.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_list_users_filtered(
+        &self,
+        filter: &UserListFilter,
+    ) -> Result<Vec<UserSchema>, AuthError> {
+        self.observe("admin_list_users_filtered", None, async {
+            self.admin_list_users_matching(filter).await
+        })
+        .await
+    }
+
+    /// Lists all soft-deleted users through the admin API
+    ///
+    /// Soft-deleted users keep their ID but have their email address and phone number
+    /// obfuscated, see [`AuthClient::soft_delete_user`]. Pass `deleted_before` to only list
+    /// users that were deleted long enough ago to be due for hard deletion.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::Http` if one of the API requests fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use time::{Duration, OffsetDateTime};
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let retention_cutoff = OffsetDateTime::now_utc() - Duration::days(30);
+    /// for user in admin_client
+    ///     .admin_list_deleted_users(Some(retention_cutoff))
+    ///     .await?
+    /// {
+    ///     admin_client.hard_delete_user(user.id).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_list_deleted_users(
+        &self,
+        deleted_before: Option<OffsetDateTime>,
+    ) -> Result<Vec<UserSchema>, AuthError> {
+        self.observe("admin_list_deleted_users", None, async {
+            let mut filter = UserListFilter::new().deleted(true);
+            filter.deleted_before = deleted_before;
+            self.admin_list_users_matching(&filter).await
+        })
+        .await
+    }
+
+    /// Looks up the user with an email address through the admin API
+    ///
+    /// The user listing is searched on the server and the results are matched exactly,
+    /// ignoring case. The address is normalized with the configured normalization first.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(user))` if a user has the email address, `Ok(None)` if not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the email address is empty.
+    /// Returns `AuthError::Http` if one of the API requests fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// if let Some(user) = admin_client.admin_get_user_by_email("user@example.com").await? {
+    ///     println!("Found user {}", user.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_get_user_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<UserSchema>, AuthError> {
+        self.observe(
+            "admin_get_user_by_email",
+            Some(mask_identifier(email)),
+            async {
+                let email = self.normalize_email(email.trim().to_string());
+                if email.is_empty() {
+                    error!("empty email");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let users = self
+                    .admin_list_users_matching(&UserListFilter::new().search(email.as_str()))
+                    .await?;
+                Ok(users.into_iter().find(|user| {
+                    user.email
+                        .as_deref()
+                        .is_some_and(|user_email| user_email.eq_ignore_ascii_case(&email))
+                }))
+            },
+        )
+        .await
+    }
+
+    /// Looks up the user with a phone number through the admin API
+    ///
+    /// The server cannot search by phone number, so this pages through all users; prefer
+    /// [`AuthClient::admin_get_user_by_email`] where possible. Numbers are compared without
+    /// a leading `+`, as GoTrue stores them.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(user))` if a user has the phone number, `Ok(None)` if not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the phone number is empty.
+    /// Returns `AuthError::Http` if one of the API requests fails.
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_get_user_by_phone(
+        &self,
+        phone: &str,
+    ) -> Result<Option<UserSchema>, AuthError> {
+        self.observe(
+            "admin_get_user_by_phone",
+            Some(mask_identifier(phone)),
+            async {
+                let phone = phone.trim().trim_start_matches('+');
+                if phone.is_empty() {
+                    error!("empty phone number");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let users = self
+                    .admin_list_users_matching(&UserListFilter::new())
+                    .await?;
+                Ok(users.into_iter().find(|user| {
+                    user.phone
+                        .as_deref()
+                        .is_some_and(|user_phone| user_phone.trim_start_matches('+') == phone)
+                }))
+            },
+        )
+        .await
+    }
+
+    pub(crate) async fn admin_list_users_matching(
+        &self,
+        filter: &UserListFilter,
+    ) -> Result<Vec<UserSchema>, AuthError> {
+        let mut matching_users = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let user_list = self
+                .admin_list_users_page(page, LIST_PAGE_SIZE, filter.search.as_deref())
+                .await?;
+            let page_len = user_list.users.len();
+
+            // Users are listed newest first, so older pages cannot match `created_after`
+            let reached_older_users = match filter.created_after {
+                Some(created_after) => user_list
+                    .users
+                    .last()
+                    .and_then(|user| user.created_at)
+                    .is_some_and(|created_at| created_at < created_after),
+                None => false,
+            };
+
+            matching_users.extend(
+                user_list
+                    .users
+                    .into_iter()
+                    .filter(|user| filter.matches(user)),
+            );
+
+            if page_len < LIST_PAGE_SIZE as usize || reached_older_users {
+                break;
+            }
+            page += 1;
+        }
+
+        info!(users = matching_users.len(), "listed filtered users");
+        Ok(matching_users)
+    }
+
+    async fn admin_list_users_page(
+        &self,
+        page: u32,
+        per_page: u32,
+        search: Option<&str>,
+    ) -> Result<UserList, AuthError> {
+        let service_role_key = self
+            .supabase_service_role_key
+            .as_ref()
+            .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+        let mut query = vec![
+            ("page", page.to_string()),
+            ("per_page", per_page.to_string()),
+            ("sort", "created_at desc".to_string()),
+        ];
+        if let Some(search) = search {
+            query.push(("filter", search.to_string()));
+        }
+
+        let resp = match self
+            .send_with_retry(|api_url| {
+                self.http_client
+                    .get(format!("{}/admin/users", api_url))
+                    .query(&query)
+                    .bearer_auth(service_role_key)
+                    .header("apiKey", service_role_key)
+            })
+            .instrument(trace_span!("gotrue admin list users"))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+
+        let resp_code_result = handle_response(&resp).await;
+        let total = resp
+            .headers()
+            .get("x-total-count")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        let linked_next_page = resp
+            .headers()
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(next_page_from_link);
+        let resp_text = match resp.text().await {
+            Ok(resp_text) => resp_text,
+            Err(e) => {
+                log::error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        debug!("resp_text: {}", self.redaction.response(&resp_text));
+        resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+        let mut user_list = match serde_json::from_str::<UserList>(&resp_text) {
+            Ok(user_list) => user_list,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        user_list.total = total;
+        user_list.next_page = match (linked_next_page, total) {
+            (Some(next_page), _) => Some(next_page),
+            (None, Some(total)) => {
+                (u64::from(page) * u64::from(per_page) < total).then_some(page + 1)
+            }
+            (None, None) => None,
+        };
+        Ok(user_list)
+    }
+}
+
+/// Returns the page number of the `rel="next"` entry of a `Link` header
+fn next_page_from_link(link: &str) -> Option<u32> {
+    link.split(',')
+        .find(|entry| entry.contains("rel=\"next\""))
+        .and_then(|entry| {
+            let target = entry.split_once('<')?.1.split_once('>')?.0;
+            let (_, query) = target.split_once('?')?;
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "page")
+                .and_then(|(_, page)| page.parse().ok())
+        })
+}
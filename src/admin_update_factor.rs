@@ -0,0 +1,112 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::admin::UpdateFactor;
+use crate::models::user::MFAFactorSchema;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Updates the attributes of a user's MFA factor through the admin API
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user owning the factor
+    /// * `factor_id` - The UUID of the factor to update
+    /// * `update` - The attributes to change
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated factor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::NotFound` if the user or factor does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::admin::UpdateFactor;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid, factor_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let factor = admin_client
+    ///     .admin_update_factor(
+    ///         user_id,
+    ///         factor_id,
+    ///         UpdateFactor {
+    ///             friendly_name: Some("Work phone".to_string()),
+    ///             ..Default::default()
+    ///         },
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_update_factor(
+        &self,
+        user_id: Uuid,
+        factor_id: Uuid,
+        update: UpdateFactor,
+    ) -> Result<MFAFactorSchema, AuthError> {
+        self.observe("admin_update_factor", Some(user_id.to_string()), async {
+            let service_role_key = self
+                .supabase_service_role_key
+                .as_ref()
+                .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .put(format!(
+                            "{}/admin/users/{}/factors/{}",
+                            api_url, user_id, factor_id
+                        ))
+                        .json(&update)
+                        .bearer_auth(service_role_key)
+                        .header("apiKey", service_role_key)
+                })
+                .instrument(trace_span!("gotrue admin update factor"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = match resp.status() {
+                reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                _ => handle_response(&resp).await,
+            };
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let factor = match serde_json::from_str::<MFAFactorSchema>(&resp_text) {
+                Ok(factor) => factor,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(factor_id = factor_id.to_string(), "updated factor");
+
+            Ok(factor)
+        })
+        .await
+    }
+}
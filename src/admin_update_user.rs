@@ -0,0 +1,159 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::admin::AdminUpdateUserParams;
+use crate::models::user::UserSchema;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Updates a user through the admin API
+    ///
+    /// Changes take effect immediately; new email addresses and phone numbers are not
+    /// confirmed by the user unless `email_confirm` / `phone_confirm` say otherwise.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user to update
+    /// * `params` - The attributes to change
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `UserSchema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the server rejects the parameters.
+    /// Returns `AuthError::WeakPassword` if the password violates the configured
+    /// [`PasswordPolicy`](crate::password_policy::PasswordPolicy).
+    /// Returns `AuthError::NotFound` if the user does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::admin::AdminUpdateUserParams;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = admin_client
+    ///     .admin_update_user(
+    ///         user_id,
+    ///         AdminUpdateUserParams {
+    ///             app_metadata: Some(HashMap::from([("plan".to_string(), "pro".into())])),
+    ///             ..Default::default()
+    ///         },
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_update_user(
+        &self,
+        user_id: Uuid,
+        mut params: AdminUpdateUserParams,
+    ) -> Result<UserSchema, AuthError> {
+        params.email = params.email.map(|email| self.normalize_email(email));
+        self.observe("admin_update_user", Some(user_id.to_string()), async {
+            let service_role_key = self
+                .supabase_service_role_key
+                .as_ref()
+                .ok_or(AuthError::ServiceRoleKeyRequired)?;
+            if let Some(password) = &params.password {
+                self.check_password_policy(password)?;
+            }
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .put(format!("{}/admin/users/{}", api_url, user_id))
+                        .json(&params)
+                        .bearer_auth(service_role_key)
+                        .header("apiKey", service_role_key)
+                })
+                .instrument(trace_span!("gotrue admin update user"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    log::error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                Ok(user) => user,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(user_id = user.id.to_string(), "updated user");
+
+            Ok(user)
+        })
+        .await
+    }
+
+    /// Bans a user for `duration` from now on through the admin API
+    ///
+    /// Banned users cannot sign in or refresh their tokens until `banned_until`, which the
+    /// returned user carries. Banning an already banned user replaces the ban.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`AuthClient::admin_update_user`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = admin_client
+    ///     .admin_ban_user(user_id, time::Duration::days(7))
+    ///     .await?;
+    /// println!("Banned until {:?}", user.banned_until);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn admin_ban_user(
+        &self,
+        user_id: Uuid,
+        duration: time::Duration,
+    ) -> Result<UserSchema, AuthError> {
+        self.admin_update_user(user_id, AdminUpdateUserParams::default().ban_for(duration))
+            .await
+    }
+
+    /// Lifts the ban of a user through the admin API
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`AuthClient::admin_update_user`].
+    pub async fn admin_unban_user(&self, user_id: Uuid) -> Result<UserSchema, AuthError> {
+        self.admin_update_user(user_id, AdminUpdateUserParams::default().unban())
+            .await
+    }
+}
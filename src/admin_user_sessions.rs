@@ -0,0 +1,221 @@
+use serde::Deserialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::admin::SessionSchema;
+use crate::models::session::SignOutScope;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+/// Session listing, sent either wrapped in an object or as a bare array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SessionsResponse {
+    Wrapped { sessions: Vec<SessionSchema> },
+    Bare(Vec<SessionSchema>),
+}
+
+impl AuthClient {
+    /// Lists the active sessions of a user through the admin API
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::NotFound` if the user does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// for session in admin_client.admin_list_user_sessions(user_id).await? {
+    ///     println!("{} from {:?} ({:?})", session.id, session.ip, session.user_agent);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_list_user_sessions(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<SessionSchema>, AuthError> {
+        self.observe(
+            "admin_list_user_sessions",
+            Some(user_id.to_string()),
+            async {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_ref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        self.http_client
+                            .get(format!("{}/admin/users/{}/sessions", api_url, user_id))
+                            .bearer_auth(service_role_key)
+                            .header("apiKey", service_role_key)
+                    })
+                    .instrument(trace_span!("gotrue admin list user sessions"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = match resp.status() {
+                    reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                    _ => handle_response(&resp).await,
+                };
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!("resp_text: {}", self.redaction.response(&resp_text));
+                resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+                match serde_json::from_str::<SessionsResponse>(&resp_text) {
+                    Ok(
+                        SessionsResponse::Wrapped { sessions } | SessionsResponse::Bare(sessions),
+                    ) => Ok(sessions),
+                    Err(e) => {
+                        error!("{}", e);
+                        Err(AuthError::Internal)
+                    }
+                }
+            },
+        )
+        .await
+    }
+
+    /// Revokes all sessions of a user through the admin API
+    ///
+    /// Refresh tokens of the user stop working immediately; access tokens already issued
+    /// remain valid until they expire, unless they are checked against the auth server.
+    /// Use this to force a compromised account to sign in again.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::NotFound` if the user does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// admin_client.admin_delete_user_sessions(user_id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn admin_delete_user_sessions(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.observe(
+            "admin_delete_user_sessions",
+            Some(user_id.to_string()),
+            async {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_ref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        self.http_client
+                            .delete(format!("{}/admin/users/{}/sessions", api_url, user_id))
+                            .bearer_auth(service_role_key)
+                            .header("apiKey", service_role_key)
+                    })
+                    .instrument(trace_span!("gotrue admin delete user sessions"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = match resp.status() {
+                    reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                    _ => handle_response(&resp).await,
+                };
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!("resp_text: {}", self.redaction.response(&resp_text));
+                resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+                info!(user_id = user_id.to_string(), "revoked user sessions");
+                Ok(())
+            },
+        )
+        .await
+    }
+
+    /// Signs a user out of their sessions through the admin API
+    ///
+    /// Invalidates the refresh tokens of the user, e.g. to enforce that a password reset
+    /// signs out every device. Only [`SignOutScope::Global`] applies to a user ID; to end
+    /// a single session or every other session, call [`AuthClient::logout_with_scope`] with
+    /// the access token of that session.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if `scope` is not [`SignOutScope::Global`].
+    /// Otherwise fails like [`AuthClient::admin_delete_user_sessions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use uuid::Uuid;
+    /// use supabase_auth_redux::models::session::SignOutScope;
+    ///
+    /// # async fn example(admin_client: AuthClient, user_id: Uuid) -> Result<(), supabase_auth_redux::AuthError> {
+    /// admin_client
+    ///     .admin_sign_out_user(user_id, SignOutScope::Global)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn admin_sign_out_user(
+        &self,
+        user_id: Uuid,
+        scope: SignOutScope,
+    ) -> Result<(), AuthError> {
+        if scope != SignOutScope::Global {
+            error!(scope = scope.as_str(), "scope requires a session");
+            return Err(AuthError::InvalidParameters(None));
+        }
+        self.admin_delete_user_sessions(user_id).await
+    }
+}
@@ -0,0 +1,100 @@
+//! axum integration
+//!
+//! [`AuthLayer`] authenticates requests carrying an `Authorization: Bearer` header and
+//! stores the outcome in the request extensions; the [`AuthenticatedUser`] and
+//! [`Claims`] extractors then reject requests that did not authenticate. Routes without
+//! these extractors stay public, so one layer can wrap a whole router.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use axum::routing::get;
+//! use axum::Router;
+//! use supabase_auth_redux::axum::{AuthLayer, AuthenticatedUser};
+//! use supabase_auth_redux::AuthClient;
+//!
+//! async fn me(AuthenticatedUser(user): AuthenticatedUser) -> String {
+//!     format!("Hello {}", user.id)
+//! }
+//!
+//! # fn example() -> Result<(), supabase_auth_redux::AuthError> {
+//! let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+//! let app: Router = Router::new()
+//!     .route("/me", get(me))
+//!     .layer(AuthLayer::server(client));
+//! # Ok(())
+//! # }
+//! ```
+
+use ::axum::extract::FromRequestParts;
+use ::axum::http::request::Parts;
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use ::axum::Json;
+
+use crate::models::claims::Claims;
+use crate::tower::AuthFailure;
+use crate::AuthError;
+
+pub use crate::tower::{AuthenticatedUser, TokenValidation};
+
+/// Layer authenticating requests with an [`AuthClient`](crate::AuthClient)
+///
+/// The axum name of [`SupabaseAuthLayer`](crate::tower::SupabaseAuthLayer).
+pub type AuthLayer = crate::tower::SupabaseAuthLayer;
+
+/// Service created by [`AuthLayer`]
+pub type AuthService<S> = crate::tower::SupabaseAuthService<S>;
+
+/// Returns the error a request failed to authenticate with
+fn rejection(parts: &Parts) -> AuthError {
+    parts
+        .extensions
+        .get::<AuthFailure>()
+        .map(|failure| failure.0.clone())
+        .unwrap_or(AuthError::NotAuthorized(None))
+}
+
+/// The user owning the request's bearer token
+///
+/// Requires an [`AuthLayer`] using [`TokenValidation::Server`]. Rejects requests without
+/// a valid token with `401 Unauthorized`.
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or_else(|| rejection(parts))
+    }
+}
+
+/// Claims of the request's bearer token
+///
+/// Requires an [`AuthLayer`] using [`TokenValidation::Local`]. Rejects requests without a
+/// valid token with `401 Unauthorized`.
+impl<S: Send + Sync> FromRequestParts<S> for Claims {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Claims>()
+            .cloned()
+            .ok_or_else(|| rejection(parts))
+    }
+}
+
+/// Responds with the error's status and its JSON serialization
+///
+/// Errors of the auth server other than a rejected token (e.g. it being unreachable)
+/// keep their own status, so they are not mistaken for bad credentials.
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,162 @@
+//! Blocking client for code that is not async
+//!
+//! Enabled with the `blocking` feature. [`BlockingAuthClient`] wraps an [`AuthClient`]
+//! and drives each operation to completion on a private single-threaded tokio runtime,
+//! so CLI tools and scripts can sign in without setting up a runtime of their own.
+//!
+//! Like `reqwest::blocking`, the client must not be used from within an async runtime;
+//! blocking on a runtime thread panics. Async code should use [`AuthClient`] directly.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use supabase_auth_redux::blocking::BlockingAuthClient;
+//! use supabase_auth_redux::IdType;
+//!
+//! # fn example() -> Result<(), supabase_auth_redux::AuthError> {
+//! let client = BlockingAuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+//! let tokens = client.signin_with_password(
+//!     IdType::Email("user@example.com".to_string()),
+//!     "secure_password".to_string(),
+//! )?;
+//! let user = client.get_user_by_token(&tokens.access_token)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+
+use tokio::runtime::{Builder, Runtime};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::models::otp::{OtpOptions, OtpType, RecoveryOptions};
+use crate::models::token::TokenResponse;
+use crate::models::user::{UserSchema, UserUpdateParams};
+use crate::{AuthClient, AuthError, IdType};
+
+/// Blocking wrapper of [`AuthClient`], see the [module documentation](self)
+pub struct BlockingAuthClient {
+    client: AuthClient,
+    runtime: Runtime,
+}
+
+impl Debug for BlockingAuthClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingAuthClient")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BlockingAuthClient {
+    /// Creates a blocking client, see [`AuthClient::new`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the URL or key is empty.
+    /// Returns `AuthError::Internal` if the runtime cannot be created.
+    pub fn new(api_url: &str, anon_key: &str) -> Result<Self, AuthError> {
+        Self::from_client(AuthClient::new(api_url, anon_key)?)
+    }
+
+    /// Wraps a configured client, e.g. one created with [`AuthClient::builder`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if the runtime cannot be created.
+    pub fn from_client(client: AuthClient) -> Result<Self, AuthError> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                error!("{}", e);
+                AuthError::Internal
+            })?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Returns the wrapped async client
+    pub fn client(&self) -> &AuthClient {
+        &self.client
+    }
+
+    fn block_on<T>(&self, operation: impl Future<Output = T>) -> T {
+        self.runtime.block_on(operation)
+    }
+
+    /// Creates a new user, see [`AuthClient::signup`]
+    pub fn signup(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(UserSchema, String), AuthError> {
+        self.block_on(self.client.signup(signup_id_type, password, metadata))
+    }
+
+    /// Signs in with an email address or phone number and a password, see
+    /// [`AuthClient::signin_with_password`]
+    pub fn signin_with_password(
+        &self,
+        id: IdType,
+        password: String,
+    ) -> Result<TokenResponse, AuthError> {
+        self.block_on(self.client.signin_with_password(id, password))
+    }
+
+    /// Sends a one-time code or magic link, see [`AuthClient::signin_with_otp`]
+    pub fn signin_with_otp(&self, id: IdType, options: OtpOptions) -> Result<(), AuthError> {
+        self.block_on(self.client.signin_with_otp(id, options))
+    }
+
+    /// Verifies a one-time code, see [`AuthClient::verify_otp`]
+    pub fn verify_otp(
+        &self,
+        id: IdType,
+        token: &str,
+        otp_type: OtpType,
+    ) -> Result<TokenResponse, AuthError> {
+        self.block_on(self.client.verify_otp(id, token, otp_type))
+    }
+
+    /// Exchanges a refresh token for new tokens, see [`AuthClient::refresh_token`]
+    pub fn refresh_token(&self, token: &str) -> Result<TokenResponse, AuthError> {
+        self.block_on(self.client.refresh_token(token))
+    }
+
+    /// Returns the user an access token belongs to, see [`AuthClient::get_user_by_token`]
+    pub fn get_user_by_token(&self, auth_token: &str) -> Result<UserSchema, AuthError> {
+        self.block_on(self.client.get_user_by_token(auth_token))
+    }
+
+    /// Updates the user an access token belongs to, see [`AuthClient::update_user`]
+    pub fn update_user(
+        &self,
+        access_token: &str,
+        params: UserUpdateParams,
+    ) -> Result<UserSchema, AuthError> {
+        self.block_on(self.client.update_user(access_token, params))
+    }
+
+    /// Sends a password recovery email, see [`AuthClient::reset_password_for_email`]
+    pub fn reset_password_for_email(
+        &self,
+        email: &str,
+        options: RecoveryOptions,
+    ) -> Result<(), AuthError> {
+        self.block_on(self.client.reset_password_for_email(email, options))
+    }
+
+    /// Ends the session of an access token, see [`AuthClient::logout`]
+    pub fn logout(&self, token: &str) -> Result<(), AuthError> {
+        self.block_on(self.client.logout(token))
+    }
+
+    /// Permanently deletes a user, see [`AuthClient::hard_delete_user`]
+    pub fn hard_delete_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.block_on(self.client.hard_delete_user(user_id))
+    }
+}
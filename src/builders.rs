@@ -0,0 +1,473 @@
+//! Fluent builders for operations with optional parameters
+//!
+//! Each builder is created from an [`AuthClient`] method (e.g. [`AuthClient::sign_up`]),
+//! collects the parameters of one request and performs it with `send()`. New optional
+//! parameters are added as builder methods, so call sites keep compiling across releases.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+use tracing::{error, instrument};
+
+use crate::error::AuthError;
+use crate::models::admin::AdminCreateUserParams;
+use crate::models::otp::{OtpChannel, OtpOptions};
+use crate::models::password::{SigninOptions, SignupOptions, SignupResult};
+use crate::models::token::TokenResponse;
+use crate::models::user::UserSchema;
+use crate::pkce::CodeChallenge;
+use crate::redaction::REDACTED;
+use crate::signup::user_and_access_token;
+use crate::{AuthClient, IdType};
+
+impl AuthClient {
+    /// Starts building a signup request
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let (user, access_token) = client
+    ///     .sign_up()
+    ///     .email("user@example.com")
+    ///     .password("secure_password")
+    ///     .metadata("full_name", "Jane Doe")
+    ///     .redirect_to("https://app.example.com/welcome")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sign_up(&self) -> SignUpBuilder<'_> {
+        SignUpBuilder {
+            client: self,
+            id: None,
+            password: String::new(),
+            options: SignupOptions::default(),
+        }
+    }
+
+    /// Starts building a password sign-in request
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let tokens = client
+    ///     .sign_in()
+    ///     .email("user@example.com")
+    ///     .password("secure_password")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sign_in(&self) -> SignInBuilder<'_> {
+        SignInBuilder {
+            client: self,
+            id: None,
+            password: String::new(),
+            options: SigninOptions::default(),
+        }
+    }
+
+    /// Starts building a one-time code / magic link request
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// client
+    ///     .sign_in_with_otp()
+    ///     .email("user@example.com")
+    ///     .should_create_user(false)
+    ///     .redirect_to("https://app.example.com/callback")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sign_in_with_otp(&self) -> OtpBuilder<'_> {
+        OtpBuilder {
+            client: self,
+            id: None,
+            options: OtpOptions::default(),
+        }
+    }
+
+    /// Starts building an admin user creation request
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = admin_client
+    ///     .admin_create()
+    ///     .email("user@example.com")
+    ///     .password("secure_password")
+    ///     .email_confirm(true)
+    ///     .app_metadata("plan", "pro")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn admin_create(&self) -> AdminCreateUserBuilder<'_> {
+        AdminCreateUserBuilder {
+            client: self,
+            params: AdminCreateUserParams::default(),
+        }
+    }
+}
+
+/// Builder for a signup request, see [`AuthClient::sign_up`]
+#[must_use = "builders do nothing until `send` is awaited"]
+pub struct SignUpBuilder<'a> {
+    client: &'a AuthClient,
+    id: Option<IdType>,
+    password: String,
+    options: SignupOptions,
+}
+
+impl Debug for SignUpBuilder<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignUpBuilder")
+            .field("client", &self.client)
+            .field("id", &self.id)
+            .field("password", &REDACTED)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl SignUpBuilder<'_> {
+    /// Signs up with an email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.id = Some(IdType::Email(email.to_string()));
+        self
+    }
+
+    /// Signs up with a phone number
+    pub fn phone(mut self, phone_number: &str) -> Self {
+        self.id = Some(IdType::PhoneNumber(phone_number.to_string()));
+        self
+    }
+
+    /// Sets the password of the new account
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = password.to_string();
+        self
+    }
+
+    /// Adds a user metadata entry
+    pub fn metadata(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.options = self.options.metadata(key, value);
+        self
+    }
+
+    /// Sets the URL the confirmation link redirects to
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.options.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Sets the channel the confirmation code is sent over to phone numbers
+    pub fn channel(mut self, channel: OtpChannel) -> Self {
+        self.options.channel = Some(channel);
+        self
+    }
+
+    /// Uses the PKCE flow for the confirmation link, see [`crate::pkce`]
+    pub fn code_challenge(mut self, challenge: CodeChallenge) -> Self {
+        self.options.code_challenge = Some(challenge);
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.options.captcha_token = Some(token.to_string());
+        self
+    }
+
+    /// Sends the signup request
+    ///
+    /// Returns the new user and an access token, like [`AuthClient::signup`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if no email address or phone number was set.
+    /// Otherwise fails like [`AuthClient::signup`].
+    #[instrument(skip_all, fields(tenant_id = self.client.context.tenant_id.as_deref(), context = %self.client.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn send(self) -> Result<(UserSchema, String), AuthError> {
+        let client = self.client;
+        let id = required_id(self.id)?;
+        client
+            .observe("signup", Some(id.to_string()), async {
+                client
+                    .signup_request(id, self.password, &self.options)
+                    .await
+                    .and_then(user_and_access_token)
+            })
+            .await
+    }
+
+    /// Sends the signup request and returns the session or the user awaiting confirmation
+    ///
+    /// See [`AuthClient::signup_with_session`].
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`SignUpBuilder::send`].
+    #[instrument(skip_all, fields(tenant_id = self.client.context.tenant_id.as_deref(), context = %self.client.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn send_with_session(self) -> Result<SignupResult, AuthError> {
+        let client = self.client;
+        let id = required_id(self.id)?;
+        client
+            .observe("signup", Some(id.to_string()), async {
+                client
+                    .signup_request(id, self.password, &self.options)
+                    .await
+            })
+            .await
+    }
+}
+
+/// Builder for a password sign-in request, see [`AuthClient::sign_in`]
+#[must_use = "builders do nothing until `send` is awaited"]
+pub struct SignInBuilder<'a> {
+    client: &'a AuthClient,
+    id: Option<IdType>,
+    password: String,
+    options: SigninOptions,
+}
+
+impl Debug for SignInBuilder<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignInBuilder")
+            .field("client", &self.client)
+            .field("id", &self.id)
+            .field("password", &REDACTED)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl SignInBuilder<'_> {
+    /// Signs in with an email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.id = Some(IdType::Email(email.to_string()));
+        self
+    }
+
+    /// Signs in with a phone number
+    pub fn phone(mut self, phone_number: &str) -> Self {
+        self.id = Some(IdType::PhoneNumber(phone_number.to_string()));
+        self
+    }
+
+    /// Sets the password
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = password.to_string();
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.options.captcha_token = Some(token.to_string());
+        self
+    }
+
+    /// Sends the sign-in request
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if no email address or phone number was set.
+    /// Otherwise fails like [`AuthClient::signin_with_password`].
+    #[instrument(skip_all, fields(tenant_id = self.client.context.tenant_id.as_deref(), context = %self.client.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn send(self) -> Result<TokenResponse, AuthError> {
+        let client = self.client;
+        let id = required_id(self.id)?;
+        client
+            .observe("signin_with_password", Some(id.to_string()), async {
+                client
+                    .signin_with_password_request(
+                        id,
+                        self.password,
+                        self.options.captcha_token.as_deref(),
+                    )
+                    .await
+            })
+            .await
+    }
+}
+
+/// Builder for a one-time code / magic link request, see [`AuthClient::sign_in_with_otp`]
+#[derive(Debug)]
+#[must_use = "builders do nothing until `send` is awaited"]
+pub struct OtpBuilder<'a> {
+    client: &'a AuthClient,
+    id: Option<IdType>,
+    options: OtpOptions,
+}
+
+impl OtpBuilder<'_> {
+    /// Sends a magic link / code to an email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.id = Some(IdType::Email(email.to_string()));
+        self
+    }
+
+    /// Sends a code to a phone number
+    pub fn phone(mut self, phone_number: &str) -> Self {
+        self.id = Some(IdType::PhoneNumber(phone_number.to_string()));
+        self
+    }
+
+    /// Sets whether unknown users are created, see [`OtpOptions::should_create_user`]
+    pub fn should_create_user(mut self, should_create_user: bool) -> Self {
+        self.options.should_create_user = should_create_user;
+        self
+    }
+
+    /// Sets the URL the magic link redirects to
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.options.email_redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Adds a metadata entry stored on the user if the user is created
+    pub fn metadata(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.options
+            .data
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Sets the channel the code is sent over to phone numbers
+    pub fn channel(mut self, channel: OtpChannel) -> Self {
+        self.options.channel = channel;
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.options.captcha_token = Some(token.to_string());
+        self
+    }
+
+    /// Uses the PKCE flow for the magic link, see [`crate::pkce`]
+    pub fn code_challenge(mut self, challenge: CodeChallenge) -> Self {
+        self.options.code_challenge = Some(challenge);
+        self
+    }
+
+    /// Sends the code or magic link
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if no email address or phone number was set.
+    /// Otherwise fails like [`AuthClient::signin_with_otp`].
+    pub async fn send(self) -> Result<(), AuthError> {
+        let id = required_id(self.id)?;
+        self.client.signin_with_otp(id, self.options).await
+    }
+}
+
+/// Builder for an admin user creation request, see [`AuthClient::admin_create`]
+#[derive(Debug)]
+#[must_use = "builders do nothing until `send` is awaited"]
+pub struct AdminCreateUserBuilder<'a> {
+    client: &'a AuthClient,
+    params: AdminCreateUserParams,
+}
+
+impl AdminCreateUserBuilder<'_> {
+    /// Sets an explicit ID for the new user
+    pub fn id(mut self, id: uuid::Uuid) -> Self {
+        self.params.id = Some(id);
+        self
+    }
+
+    /// Sets the email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.params.email = Some(email.to_string());
+        self
+    }
+
+    /// Sets the phone number
+    pub fn phone(mut self, phone_number: &str) -> Self {
+        self.params.phone = Some(phone_number.to_string());
+        self
+    }
+
+    /// Sets a plain text password
+    pub fn password(mut self, password: &str) -> Self {
+        self.params.password = Some(password.to_string());
+        self
+    }
+
+    /// Sets an already hashed password
+    pub fn password_hash(mut self, password_hash: &str) -> Self {
+        self.params.password_hash = Some(password_hash.to_string());
+        self
+    }
+
+    /// Marks the email address as confirmed
+    pub fn email_confirm(mut self, confirmed: bool) -> Self {
+        self.params.email_confirm = confirmed;
+        self
+    }
+
+    /// Marks the phone number as confirmed
+    pub fn phone_confirm(mut self, confirmed: bool) -> Self {
+        self.params.phone_confirm = confirmed;
+        self
+    }
+
+    /// Bans the new user for a duration such as `"24h"` (Go duration syntax)
+    pub fn ban_duration(mut self, duration: &str) -> Self {
+        self.params.ban_duration = Some(duration.to_string());
+        self
+    }
+
+    /// Adds a user metadata entry
+    pub fn user_metadata(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.params
+            .user_metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Adds an app metadata entry
+    pub fn app_metadata(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.params
+            .app_metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Creates the user
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`AuthClient::admin_create_user`].
+    pub async fn send(self) -> Result<UserSchema, AuthError> {
+        self.client.admin_create_user(self.params).await
+    }
+}
+
+fn required_id(id: Option<IdType>) -> Result<IdType, AuthError> {
+    id.ok_or_else(|| {
+        error!("no email or phone number set");
+        AuthError::InvalidParameters(None)
+    })
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use crate::AuthClient;
+
+/// Caller supplied context recorded on the tracing span of every operation
+///
+/// Multi-tenant services can attach the tenant and the inbound request ID to the client
+/// with [`AuthClient::with_context`], which makes every log event emitted during an auth
+/// operation (including the failure logs) carry these fields. Errors returned with API
+/// error details carry the context as well, see [`AuthError::context`](crate::AuthError::context).
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::OperationContext;
+///
+/// let context = OperationContext::new()
+///     .tenant_id("acme")
+///     .request_id("req-42")
+///     .attribute("feature", "new-onboarding");
+///
+/// assert_eq!(context.to_string(), "tenant_id=acme request_id=req-42 feature=new-onboarding");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationContext {
+    /// Tenant the operation is performed for
+    pub tenant_id: Option<String>,
+    /// Request or correlation ID of the caller
+    pub request_id: Option<String>,
+    /// Additional key/value pairs such as feature flags
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl OperationContext {
+    /// Creates an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the tenant ID
+    pub fn tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Sets the request ID
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Adds an additional key/value pair
+    pub fn attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns `true` if no context has been set
+    pub fn is_empty(&self) -> bool {
+        self.tenant_id.is_none() && self.request_id.is_none() && self.attributes.is_empty()
+    }
+}
+
+impl Display for OperationContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tenant_id = self.tenant_id.as_ref().map(|v| ("tenant_id", v.as_str()));
+        let request_id = self.request_id.as_ref().map(|v| ("request_id", v.as_str()));
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()));
+
+        let pairs = tenant_id.into_iter().chain(request_id).chain(attributes);
+        for (i, (key, value)) in pairs.enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl AuthClient {
+    /// Returns a client that records `context` on the span of every operation
+    ///
+    /// Failed operations attach `context` to the [`ApiError`](crate::ApiError) details of
+    /// the returned error.
+    ///
+    /// The returned client shares the underlying HTTP connection pool, so it is cheap to
+    /// create one per inbound request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, OperationContext};
+    /// # async fn example(access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let user = client
+    ///     .with_context(OperationContext::new().tenant_id("acme").request_id("req-42"))
+    ///     .get_user_by_token(access_token)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_context(&self, context: OperationContext) -> AuthClient {
+        AuthClient {
+            context: Arc::new(context),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the context recorded on the spans of this client's operations
+    pub fn context(&self) -> &OperationContext {
+        &self.context
+    }
+}
@@ -0,0 +1,360 @@
+//! Session cookies compatible with `@supabase/ssr`
+//!
+//! Server rendered apps keep the session in cookies that the browser and the server both
+//! read and write. [`AuthClient::get_session_from_cookies`] reads the session supabase-js
+//! stored in a request's cookies, refreshing it when the access token is about to expire,
+//! and [`AuthClient::set_session_cookies`] writes a session back in the same format, so
+//! Rust servers and supabase-js clients share one sign-in. Cookies are sent by the browser,
+//! so the session's access token is verified before its user is trusted.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use http::HeaderMap;
+//! use supabase_auth_redux::cookie::CookieOptions;
+//! use supabase_auth_redux::AuthClient;
+//!
+//! # async fn example(client: AuthClient, request_headers: HeaderMap) -> Result<(), supabase_auth_redux::AuthError> {
+//! let options = CookieOptions::new().secure(true);
+//! let mut response_headers = HeaderMap::new();
+//! if let Some(cookie_session) = client.get_session_from_cookies(&request_headers, &options).await? {
+//!     if cookie_session.refreshed {
+//!         client.set_session_cookies(
+//!             &request_headers,
+//!             &mut response_headers,
+//!             Some(&cookie_session.session),
+//!             &options,
+//!         )?;
+//!     }
+//!     println!("signed in as {:?}", cookie_session.session.user.map(|user| user.id));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use http::header::{COOKIE, SET_COOKIE};
+use http::{HeaderMap, HeaderValue};
+use tracing::{debug, error, info};
+use url::Url;
+
+use crate::models::session::Session;
+use crate::{AuthClient, AuthError};
+
+/// How long before its expiry an access token read from cookies is refreshed
+const REFRESH_MARGIN: Duration = Duration::from_secs(10);
+
+/// Lifetime `@supabase/ssr` gives session cookies: 400 days, the maximum browsers allow
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(400 * 24 * 60 * 60);
+
+/// `SameSite` attribute of session cookies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SameSite {
+    /// Sent with same-site requests and top-level navigations
+    #[default]
+    Lax,
+    /// Sent with same-site requests only
+    Strict,
+    /// Sent with all requests; requires `Secure`
+    None,
+}
+
+impl SameSite {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Lax => "Lax",
+            SameSite::Strict => "Strict",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// Name and attributes of session cookies
+///
+/// The defaults match `@supabase/ssr`: the cookie is named after the project
+/// (`sb-<project_ref>-auth-token`), valid for the whole site for 400 days and readable by
+/// browser JavaScript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieOptions {
+    /// Cookie name, derived from the API URL when `None`
+    pub name: Option<String>,
+    /// `Path` attribute
+    pub path: String,
+    /// `Domain` attribute
+    pub domain: Option<String>,
+    /// `Max-Age` attribute
+    pub max_age: Duration,
+    /// `SameSite` attribute
+    pub same_site: SameSite,
+    /// Whether the `Secure` attribute is set
+    pub secure: bool,
+    /// Whether the `HttpOnly` attribute is set, hiding the session from supabase-js
+    pub http_only: bool,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            path: "/".to_string(),
+            domain: None,
+            max_age: DEFAULT_MAX_AGE,
+            same_site: SameSite::default(),
+            secure: false,
+            http_only: false,
+        }
+    }
+}
+
+impl CookieOptions {
+    /// Creates options matching the defaults of `@supabase/ssr`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cookie name, e.g. to match a custom `cookieOptions.name` of supabase-js
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Sets the `Path` attribute
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Sets the `Domain` attribute
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    /// Sets the `Max-Age` attribute
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Sets the `SameSite` attribute
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Sets whether the `Secure` attribute is set
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Sets whether the `HttpOnly` attribute is set
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    fn set_cookie(&self, name: &str, value: &str, max_age: Duration) -> String {
+        let mut cookie = format!(
+            "{}={}; Path={}; Max-Age={}; SameSite={}",
+            name,
+            value,
+            self.path,
+            max_age.as_secs(),
+            self.same_site.as_str()
+        );
+        if let Some(domain) = &self.domain {
+            cookie.push_str("; Domain=");
+            cookie.push_str(domain);
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        if self.http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        cookie
+    }
+}
+
+/// A session read from request cookies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieSession {
+    /// The session, refreshed if its access token was about to expire
+    pub session: Session,
+    /// Whether the session was refreshed; write it back with
+    /// [`AuthClient::set_session_cookies`] so the browser keeps the new refresh token
+    pub refreshed: bool,
+}
+
+/// Returns the cookies of all `Cookie` headers as name and value pairs
+fn request_cookies(headers: &HeaderMap) -> Vec<(&str, &str)> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .collect()
+}
+
+/// Returns whether `cookie` is the session cookie `name` or one of its chunks
+fn is_session_cookie(cookie: &str, name: &str) -> bool {
+    match cookie.strip_prefix(name) {
+        Some("") => true,
+        Some(suffix) => suffix
+            .strip_prefix('.')
+            .is_some_and(|index| index.parse::<usize>().is_ok()),
+        None => false,
+    }
+}
+
+/// Returns the session cookie value, joining its chunks if it was split
+fn session_cookie_value(cookies: &[(&str, &str)], name: &str) -> Option<String> {
+    if let Some((_, value)) = cookies.iter().find(|(cookie, _)| *cookie == name) {
+        return Some(value.to_string());
+    }
+    let mut value = String::new();
+    for index in 0.. {
+        let chunk_name = format!("{}.{}", name, index);
+        match cookies.iter().find(|(cookie, _)| *cookie == chunk_name) {
+            Some((_, chunk)) => value.push_str(chunk),
+            None => break,
+        }
+    }
+    (!value.is_empty()).then_some(value)
+}
+
+impl AuthClient {
+    /// Returns the name `@supabase/ssr` stores the session cookie under for this project
+    ///
+    /// The name is `sb-<first label of the API host>-auth-token`, e.g.
+    /// `sb-abcdefghijklmnopqrst-auth-token` for `https://abcdefghijklmnopqrst.supabase.co`.
+    pub fn session_cookie_name(&self) -> String {
+        let host = Url::parse(self.api_urls.primary())
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let project = host.split('.').next().unwrap_or_default();
+        format!("sb-{}-auth-token", project)
+    }
+
+    /// Reads the session stored in a request's cookies by supabase-js or
+    /// [`AuthClient::set_session_cookies`]
+    ///
+    /// Sessions whose access token expires within 10 seconds are refreshed; write a
+    /// refreshed session back to the response with [`AuthClient::set_session_cookies`].
+    ///
+    /// The access token is verified locally with the configured
+    /// [`JwtVerifier`](crate::jwt::JwtVerifier), rejecting sessions whose user does not
+    /// match the token's subject. Without a verifier the user is fetched with
+    /// [`AuthClient::get_user_by_token`], unless the refresh just returned it. Either way
+    /// `session.user` can be trusted as the signed in user.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the request carries no session cookie.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the cookie does not hold a session.
+    /// Returns `AuthError::NotAuthorized` if the access token is rejected or belongs to
+    /// another user than the cookie's.
+    /// Otherwise fails like [`AuthClient::refresh_token`] if the session cannot be refreshed,
+    /// or like [`AuthClient::get_user_by_token`] if the user cannot be fetched.
+    pub async fn get_session_from_cookies(
+        &self,
+        headers: &HeaderMap,
+        options: &CookieOptions,
+    ) -> Result<Option<CookieSession>, AuthError> {
+        let name = options
+            .name
+            .clone()
+            .unwrap_or_else(|| self.session_cookie_name());
+        let cookies = request_cookies(headers);
+        let Some(value) = session_cookie_value(&cookies, &name) else {
+            debug!("no session cookie");
+            return Ok(None);
+        };
+
+        let mut session = Session::from_cookie_value(&value)?;
+        let refreshed = session.expires_within(REFRESH_MARGIN);
+        if refreshed {
+            let tokens = self.refresh_token(&session.refresh_token).await?;
+            session = Session::from(tokens);
+            info!("refreshed session from cookies");
+        }
+        if self.jwt_verifier.is_some() {
+            let claims = self.verify_access_token(&session.access_token)?;
+            if session
+                .user
+                .as_ref()
+                .is_some_and(|user| user.id != claims.sub)
+            {
+                error!("session cookie user does not match the access token");
+                return Err(AuthError::NotAuthorized(None));
+            }
+        } else if !(refreshed && session.user.is_some()) {
+            session.user = Some(self.get_user_by_token(&session.access_token).await?);
+        }
+
+        Ok(Some(CookieSession { session, refreshed }))
+    }
+
+    /// Writes `session` to the `Set-Cookie` headers of a response
+    ///
+    /// The session is encoded like `@supabase/ssr` does, split into chunks if it is too
+    /// long for a single cookie. Session cookies of the request that are no longer needed,
+    /// such as chunks of a longer previous session, are expired. Passing `None` expires all
+    /// of them, signing the browser out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if the session cannot be encoded.
+    pub fn set_session_cookies(
+        &self,
+        request_headers: &HeaderMap,
+        response_headers: &mut HeaderMap,
+        session: Option<&Session>,
+        options: &CookieOptions,
+    ) -> Result<(), AuthError> {
+        let name = options
+            .name
+            .clone()
+            .unwrap_or_else(|| self.session_cookie_name());
+
+        let mut set_cookies = Vec::new();
+        let mut written = Vec::new();
+        if let Some(session) = session {
+            let chunks = session.to_cookie_chunks()?;
+            if let [value] = chunks.as_slice() {
+                set_cookies.push(options.set_cookie(&name, value, options.max_age));
+                written.push(name.clone());
+            } else {
+                for (index, chunk) in chunks.iter().enumerate() {
+                    let chunk_name = format!("{}.{}", name, index);
+                    set_cookies.push(options.set_cookie(&chunk_name, chunk, options.max_age));
+                    written.push(chunk_name);
+                }
+            }
+        }
+        for (cookie, _) in request_cookies(request_headers) {
+            if is_session_cookie(cookie, &name) && !written.iter().any(|name| name == cookie) {
+                set_cookies.push(options.set_cookie(cookie, "", Duration::ZERO));
+            }
+        }
+
+        for set_cookie in set_cookies {
+            let value = match HeaderValue::from_str(&set_cookie) {
+                Ok(value) => value,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            response_headers.append(SET_COOKIE, value);
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
-use crate::util::handle_response_code;
+use crate::util::{Credentials, Endpoint};
 use crate::AuthClient;
 use crate::AuthError;
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, instrument};
+use tracing::instrument;
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -42,44 +43,14 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip_all)]
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn soft_delete_user(&self, user_id: Uuid) -> Result<(), AuthError> {
-        let service_role_key = self
-            .supabase_service_role_key
-            .as_ref()
-            .ok_or(AuthError::ServiceRoleKeyRequired)?;
-
-        let resp = match self
-            .http_client
-            .delete(format!(
-                "{}/auth/v1/admin/users/{}",
-                self.supabase_api_url, user_id
-            ))
-            .json(&DeleteBody {
-                should_soft_delete: true,
-            })
-            .bearer_auth(service_role_key)
-            .header("apiKey", service_role_key)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                debug!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                log::error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result
+        self.observe(
+            "soft_delete_user",
+            Some(user_id.to_string()),
+            self.delete_user_request(user_id, true),
+        )
+        .await
     }
 
     /// Permanently deletes a user and all their associated data
@@ -114,43 +85,29 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip_all)]
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn hard_delete_user(&self, user_id: Uuid) -> Result<(), AuthError> {
-        let service_role_key = self
-            .supabase_service_role_key
-            .as_ref()
-            .ok_or(AuthError::ServiceRoleKeyRequired)?;
-
-        let resp = match self
-            .http_client
-            .delete(format!(
-                "{}/auth/v1/admin/users/{}",
-                self.supabase_api_url, user_id
-            ))
-            .json(&DeleteBody {
-                should_soft_delete: false,
-            })
-            .bearer_auth(service_role_key)
-            .header("apiKey", service_role_key)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                debug!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
+        self.observe(
+            "hard_delete_user",
+            Some(user_id.to_string()),
+            self.delete_user_request(user_id, false),
+        )
+        .await
+    }
 
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                log::error!("{}", e);
-                return Err(AuthError::Http);
-            }
+    async fn delete_user_request(&self, user_id: Uuid, soft: bool) -> Result<(), AuthError> {
+        let body = DeleteBody {
+            should_soft_delete: soft,
         };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result
+        self.execute_text(
+            Endpoint::new(
+                Method::DELETE,
+                format!("admin/users/{}", user_id),
+                Credentials::ServiceRole,
+            )
+            .json(&body),
+        )
+        .await?;
+        Ok(())
     }
 }
@@ -0,0 +1,204 @@
+use serde::Deserialize;
+use tracing::{error, info, instrument};
+
+use crate::error::AuthError;
+use crate::models::otp::OtpType;
+use crate::models::session::Session;
+use crate::models::token::TokenResponse;
+use crate::models::user::{EmailChangeVerification, UserSchema, UserUpdateParams};
+use crate::util::mask_identifier;
+use crate::verify_otp::VerifyRequest;
+use crate::{AuthClient, IdType};
+
+/// Response to the first of two email change confirmations
+#[derive(Debug, Deserialize)]
+struct SingleConfirmationResponse {
+    msg: String,
+}
+
+impl AuthClient {
+    /// Starts changing the email address of the user owning an access token
+    ///
+    /// GoTrue emails a confirmation link (and code) to the new address and, with secure
+    /// email change enabled, to the current address as well. The returned user lists the
+    /// new address as [`UserSchema::pending_email_change`] until the change is confirmed
+    /// with [`AuthClient::verify_email_change`] or [`AuthClient::verify_email_change_otp`].
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `new_email` - The new email address
+    /// * `redirect_to` - URL the confirmation links redirect to; the configured default
+    ///   redirect URL is used when `None`
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the new email address is not valid.
+    /// Otherwise fails like [`AuthClient::update_user`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = client
+    ///     .request_email_change(
+    ///         access_token,
+    ///         "new@example.com",
+    ///         Some("https://app.example.com/auth/confirm"),
+    ///     )
+    ///     .await?;
+    /// if let Some(pending) = user.pending_email_change() {
+    ///     println!("Confirmation sent to {} at {:?}", pending.value, pending.sent_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_email_change(
+        &self,
+        access_token: &str,
+        new_email: &str,
+        redirect_to: Option<&str>,
+    ) -> Result<UserSchema, AuthError> {
+        IdType::email(new_email)?;
+        let mut params = UserUpdateParams::new().email(new_email.trim());
+        if let Some(redirect_to) = redirect_to {
+            params = params.email_redirect_to(redirect_to);
+        }
+        self.update_user(access_token, params).await
+    }
+
+    /// Confirms an email address change with the token hash of a confirmation link
+    ///
+    /// Call this from the endpoint the `email_change` confirmation links point to. With
+    /// secure email change enabled, the first of the two links is acknowledged with
+    /// [`EmailChangeVerification::AwaitingOtherConfirmation`]; the second completes the
+    /// change and signs the user in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token hash is empty.
+    /// Returns `AuthError::NotAuthorized` if the token hash is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::user::EmailChangeVerification;
+    ///
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// // Handling GET /auth/confirm?token_hash=abc123&type=email_change
+    /// match client.verify_email_change("abc123").await? {
+    ///     EmailChangeVerification::AwaitingOtherConfirmation { message } => println!("{}", message),
+    ///     EmailChangeVerification::Completed(session) => {
+    ///         println!("Email changed, signed in until {}", session.expires_at)
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn verify_email_change(
+        &self,
+        token_hash: &str,
+    ) -> Result<EmailChangeVerification, AuthError> {
+        self.observe("verify_email_change", None, async {
+            if token_hash.is_empty() {
+                error!("empty token hash");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let body = VerifyRequest {
+                otp_type: OtpType::EmailChange,
+                token: None,
+                token_hash: Some(token_hash),
+                email: None,
+                phone: None,
+            };
+            self.email_change_verification(&body).await
+        })
+        .await
+    }
+
+    /// Confirms an email address change with the code sent to one of the addresses
+    ///
+    /// Works like [`AuthClient::verify_email_change`] for email templates sending codes
+    /// instead of links.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - The address the code was sent to, the current or the new one
+    /// * `token` - The code from the email
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the email address or code is empty.
+    /// Returns `AuthError::NotAuthorized` if the code is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn verify_email_change_otp(
+        &self,
+        email: &str,
+        token: &str,
+    ) -> Result<EmailChangeVerification, AuthError> {
+        self.observe(
+            "verify_email_change_otp",
+            Some(mask_identifier(email)),
+            async {
+                let email = self.normalize_email(email.to_string());
+                if email.is_empty() || token.is_empty() {
+                    error!("empty email or token");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let body = VerifyRequest {
+                    otp_type: OtpType::EmailChange,
+                    token: Some(token),
+                    token_hash: None,
+                    email: Some(&email),
+                    phone: None,
+                };
+                self.email_change_verification(&body).await
+            },
+        )
+        .await
+    }
+
+    /// Sends an `email_change` verification and tells a completed change from the first
+    /// of two confirmations
+    async fn email_change_verification(
+        &self,
+        body: &VerifyRequest<'_>,
+    ) -> Result<EmailChangeVerification, AuthError> {
+        let resp_text = self.verify_request_text(body).await?;
+
+        // The first of two confirmations is acknowledged with a message instead of tokens
+        let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+            Ok(token_response) => token_response,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        if !token_response.access_token.is_empty() {
+            info!("email change completed");
+            return Ok(EmailChangeVerification::Completed(Box::new(Session::from(
+                token_response,
+            ))));
+        }
+
+        match serde_json::from_str::<SingleConfirmationResponse>(&resp_text) {
+            Ok(confirmation) => {
+                info!("email change awaiting other confirmation");
+                Ok(EmailChangeVerification::AwaitingOtherConfirmation {
+                    message: confirmation.msg,
+                })
+            }
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+}
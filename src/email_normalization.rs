@@ -0,0 +1,76 @@
+//! Opt-in normalization of email addresses
+//!
+//! GoTrue compares email addresses as given, so `Jane@Example.com` and `jane@example.com`
+//! end up as two accounts. Configuring an [`EmailNormalization`] on the client normalizes
+//! every email address before it is sent for signup, sign-in and user creation.
+
+use crate::{AuthClient, IdType};
+
+/// Domains whose local part ignores dots
+const GMAIL_DOMAINS: [&str; 2] = ["gmail.com", "googlemail.com"];
+
+/// How email addresses are normalized before they are sent to the server
+///
+/// Addresses are always trimmed and lowercased; folding the dots of Gmail addresses is
+/// optional.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::email_normalization::EmailNormalization;
+///
+/// let normalization = EmailNormalization::new().fold_gmail_dots(true);
+///
+/// assert_eq!(normalization.normalize(" Jane.Doe@Example.com "), "jane.doe@example.com");
+/// assert_eq!(normalization.normalize("Jane.Doe@gmail.com"), "janedoe@gmail.com");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmailNormalization {
+    /// Remove dots from the local part of `gmail.com` and `googlemail.com` addresses
+    pub fold_gmail_dots: bool,
+}
+
+impl EmailNormalization {
+    /// Creates a normalization that trims and lowercases addresses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether dots are removed from the local part of Gmail addresses
+    pub fn fold_gmail_dots(mut self, fold_gmail_dots: bool) -> Self {
+        self.fold_gmail_dots = fold_gmail_dots;
+        self
+    }
+
+    /// Returns the normalized form of `email`
+    pub fn normalize(&self, email: &str) -> String {
+        let email = email.trim().to_lowercase();
+        if !self.fold_gmail_dots {
+            return email;
+        }
+        match email.rsplit_once('@') {
+            Some((local, domain)) if GMAIL_DOMAINS.contains(&domain) => {
+                format!("{}@{}", local.replace('.', ""), domain)
+            }
+            _ => email,
+        }
+    }
+}
+
+impl AuthClient {
+    /// Normalizes `email` with the configured normalization, if any
+    pub(crate) fn normalize_email(&self, email: String) -> String {
+        match &self.email_normalization {
+            Some(normalization) => normalization.normalize(&email),
+            None => email,
+        }
+    }
+
+    /// Normalizes the email address of `id` with the configured normalization, if any
+    pub(crate) fn normalize_id(&self, id: IdType) -> IdType {
+        match id {
+            IdType::Email(email) => IdType::Email(self.normalize_email(email)),
+            IdType::PhoneNumber(phone_number) => IdType::PhoneNumber(phone_number),
+        }
+    }
+}
@@ -1,19 +1,126 @@
 #![allow(missing_docs)]
 
 use kinded::Kinded;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
+use crate::context::OperationContext;
+use crate::GoTrueErrorResponse;
+
+/// GoTrue error codes reporting that the email address or phone number is taken
+const USER_EXISTS_CODES: &[&str] = &["user_already_exists", "email_exists", "phone_exists"];
+
+/// GoTrue error code reporting that a password violates the password requirements
+const WEAK_PASSWORD_CODE: &str = "weak_password";
+
+/// GoTrue error code reporting that a rotated out refresh token was presented again
+const REFRESH_TOKEN_REUSED_CODE: &str = "refresh_token_already_used";
+
+/// GoTrue error code reporting that a refresh token does not exist or was revoked
+const REFRESH_TOKEN_NOT_FOUND_CODE: &str = "refresh_token_not_found";
+
+/// Error response received from the auth API
+///
+/// Carried by the variants of [`AuthError`] that map API error responses, so the server's
+/// message and error code stay available. Errors raised by the client itself (e.g. an empty
+/// email address) carry none, except for identifier validation, which describes the problem
+/// with a `validation_failed` error.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// HTTP status code of the response
+    pub status: u16,
+    /// GoTrue error code (e.g. `user_already_exists`), from `error_code` or, on older
+    /// versions, the OAuth style `error` field
+    pub error_code: Option<String>,
+    /// Parsed response body, if it was a GoTrue error body
+    pub response: Option<GoTrueErrorResponse>,
+    /// ID the server or gateway assigned to the request, from the `x-request-id` or
+    /// `sb-request-id` response header; quote it when contacting Supabase support
+    pub request_id: Option<String>,
+    /// `sb-gateway-*` response headers (e.g. `sb-gateway-version`) as name and value pairs
+    pub gateway_headers: Vec<(String, String)>,
+    /// Context of the client that performed the failed operation, see
+    /// [`AuthClient::with_context`](crate::AuthClient::with_context)
+    pub context: Option<OperationContext>,
+}
+
+impl ApiError {
+    pub(crate) fn new(status: u16) -> Self {
+        Self {
+            status,
+            error_code: None,
+            response: None,
+            request_id: None,
+            gateway_headers: Vec::new(),
+            context: None,
+        }
+    }
+
+    /// Describes an invalid parameter detected before a request is sent
+    pub(crate) fn validation(message: String) -> Self {
+        Self {
+            status: 400,
+            error_code: Some("validation_failed".to_string()),
+            response: Some(GoTrueErrorResponse {
+                code: Some(400),
+                error: None,
+                error_description: None,
+                msg: Some(message),
+            }),
+            request_id: None,
+            gateway_headers: Vec::new(),
+            context: None,
+        }
+    }
+
+    /// Returns the human readable message sent by the server
+    pub fn message(&self) -> Option<&str> {
+        let response = self.response.as_ref()?;
+        response
+            .msg
+            .as_deref()
+            .or(response.error_description.as_deref())
+            .or(response.error.as_deref())
+    }
+}
+
+/// Returns the `weak_password.reasons` of an error response body
+fn body_reasons(resp_text: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(resp_text)
+        .ok()
+        .and_then(|body| body.get("weak_password")?.get("reasons").cloned())
+        .and_then(|reasons| serde_json::from_value(reasons).ok())
+        .unwrap_or_default()
+}
+
+fn describe(api_error: &Option<Box<ApiError>>) -> String {
+    let Some(api_error) = api_error.as_deref() else {
+        return String::new();
+    };
+    let mut description = match api_error.message() {
+        Some(message) => format!(": {}", message),
+        None => String::new(),
+    };
+    if let Some(request_id) = &api_error.request_id {
+        description.push_str(&format!(" (request id {})", request_id));
+    }
+    description
+}
+
 /// Errors that can occur when interacting with the Supabase Auth API
-#[derive(Debug, Default, Clone, Copy, Error, Kinded)]
+///
+/// Match on [`AuthError::kind`] to handle errors regardless of the details they carry.
+#[derive(Debug, Clone, Error, Kinded)]
 #[non_exhaustive]
 pub enum AuthError {
     /// User is not authorized to perform the requested operation
-    #[error("not authorized")]
-    NotAuthorized,
+    #[error("not authorized{}", describe(.0))]
+    NotAuthorized(Option<Box<ApiError>>),
 
     /// Invalid parameters provided to the API
-    #[error("invalid parameters")]
-    InvalidParameters,
+    #[error("invalid parameters{}", describe(.0))]
+    InvalidParameters(Option<Box<ApiError>>),
 
     /// HTTP communication error
     #[error("http error")]
@@ -24,15 +131,279 @@ pub enum AuthError {
     Internal,
 
     /// Requested resource was not found
-    #[error("resource not found")]
-    NotFound,
+    #[error("resource not found{}", describe(.0))]
+    NotFound(Option<Box<ApiError>>),
+
+    /// No user exists for the given email address or phone number
+    #[error("user not found")]
+    UserNotFound,
+
+    /// The user was created but has to confirm the email address or phone number before a
+    /// session is issued
+    ///
+    /// Returned by the signup methods returning an access token; use
+    /// [`AuthClient::signup_with_session`](crate::AuthClient::signup_with_session) to get
+    /// the unconfirmed user instead.
+    #[error("confirmation required")]
+    ConfirmationRequired,
+
+    /// A user with the given email address or phone number already exists
+    #[error("user already exists{}", describe(.0))]
+    UserAlreadyExists(Option<Box<ApiError>>),
 
     /// Service role key is required for admin operations
     #[error("service role key required for admin operations")]
     ServiceRoleKeyRequired,
 
+    /// Password does not satisfy the password policy
+    ///
+    /// `reasons` lists the violated requirements as sent by GoTrue (`length`,
+    /// `characters` or `pwned`) or found by the client-side password policy.
+    #[error("weak password{}", describe(.api_error))]
+    WeakPassword {
+        reasons: Vec<String>,
+        api_error: Option<Box<ApiError>>,
+    },
+
+    /// A refresh token that was already rotated out was presented again, which indicates
+    /// the token may have been stolen
+    ///
+    /// Also returned for GoTrue's `refresh_token_already_used` error. If several processes
+    /// share a session, another one may have rotated the token: load the latest session
+    /// (e.g. from a shared [`SessionStore`](crate::SessionStore)) and retry with its
+    /// refresh token before signing the user out.
+    #[error("refresh token reused{}", describe(.0))]
+    RefreshTokenReused(Option<Box<ApiError>>),
+
+    /// The refresh token does not exist or was revoked, e.g. because the user signed out
+    ///
+    /// Retrying cannot succeed; the user has to sign in again.
+    #[error("refresh token not found{}", describe(.0))]
+    RefreshTokenNotFound(Option<Box<ApiError>>),
+
+    /// Operation did not complete within the allotted time
+    #[error("operation timed out")]
+    Timeout,
+
+    /// Too many requests were sent, e.g. too many emails to the same address
+    ///
+    /// `retry_after` is the delay the server asked for in its `Retry-After` header.
+    #[error("rate limited{}", describe(.api_error))]
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+        api_error: Option<Box<ApiError>>,
+    },
+
     /// General authentication error
-    #[error("general gotrue error")]
-    #[default]
-    GeneralError,
+    #[error("general gotrue error{}", describe(.0))]
+    GeneralError(Option<Box<ApiError>>),
+}
+
+impl Default for AuthError {
+    fn default() -> Self {
+        AuthError::GeneralError(None)
+    }
+}
+
+impl AuthError {
+    /// Returns the API error response the error was mapped from, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// # async fn example(client: AuthClient) {
+    /// let result = client
+    ///     .signup(IdType::Email("user@example.com".to_string()), "password".to_string(), None)
+    ///     .await;
+    /// if let Err(e) = result {
+    ///     if let Some(api_error) = e.api_error() {
+    ///         println!("{:?}: {:?}", api_error.error_code, api_error.message());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            AuthError::NotAuthorized(api_error)
+            | AuthError::InvalidParameters(api_error)
+            | AuthError::NotFound(api_error)
+            | AuthError::UserAlreadyExists(api_error)
+            | AuthError::RateLimited { api_error, .. }
+            | AuthError::WeakPassword { api_error, .. }
+            | AuthError::RefreshTokenReused(api_error)
+            | AuthError::RefreshTokenNotFound(api_error)
+            | AuthError::GeneralError(api_error) => api_error.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the GoTrue error code of the API error response, if any
+    pub fn error_code(&self) -> Option<&str> {
+        self.api_error()?.error_code.as_deref()
+    }
+
+    /// Returns the ID of the failed request, to correlate it with the server's logs
+    ///
+    /// Taken from the `x-request-id` or `sb-request-id` response header, so only errors
+    /// mapped from a response sent through the Supabase gateway carry one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.api_error()?.request_id.as_deref()
+    }
+
+    /// Returns the context of the client that performed the failed operation
+    ///
+    /// Set on errors carrying [`ApiError`] details when the operation ran on a client
+    /// created with [`AuthClient::with_context`](crate::AuthClient::with_context).
+    pub fn context(&self) -> Option<&OperationContext> {
+        self.api_error()?.context.as_ref()
+    }
+
+    /// Returns the delay the server asked for before retrying, if it rate limited the request
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            AuthError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Attaches the operation context to the error's API error details, if it is set
+    pub(crate) fn with_context(mut self, context: &OperationContext) -> Self {
+        if context.is_empty() {
+            return self;
+        }
+        if let AuthError::NotAuthorized(Some(api_error))
+        | AuthError::InvalidParameters(Some(api_error))
+        | AuthError::NotFound(Some(api_error))
+        | AuthError::UserAlreadyExists(Some(api_error))
+        | AuthError::RateLimited {
+            api_error: Some(api_error),
+            ..
+        }
+        | AuthError::WeakPassword {
+            api_error: Some(api_error),
+            ..
+        }
+        | AuthError::RefreshTokenReused(Some(api_error))
+        | AuthError::RefreshTokenNotFound(Some(api_error))
+        | AuthError::GeneralError(Some(api_error)) = &mut self
+        {
+            api_error.context = Some(context.clone());
+        }
+        self
+    }
+
+    /// Attaches the body of an error response to the error's API error details
+    ///
+    /// Errors reporting a taken email address or phone number become
+    /// [`AuthError::UserAlreadyExists`]; rejected refresh tokens become
+    /// [`AuthError::RefreshTokenReused`] or [`AuthError::RefreshTokenNotFound`].
+    pub(crate) fn with_response_body(mut self, resp_text: &str) -> Self {
+        if let AuthError::NotAuthorized(Some(api_error))
+        | AuthError::InvalidParameters(Some(api_error))
+        | AuthError::NotFound(Some(api_error))
+        | AuthError::RateLimited {
+            api_error: Some(api_error),
+            ..
+        }
+        | AuthError::GeneralError(Some(api_error)) = &mut self
+        {
+            let body = serde_json::from_str::<serde_json::Value>(resp_text).ok();
+            api_error.error_code = body.as_ref().and_then(|body| {
+                body.get("error_code")
+                    .or_else(|| body.get("error"))
+                    .and_then(|code| code.as_str())
+                    .map(str::to_string)
+            });
+            api_error.response = body.and_then(|body| serde_json::from_value(body).ok());
+
+            // Older GoTrue versions only report the condition in the message
+            let user_exists = api_error
+                .error_code
+                .as_deref()
+                .is_some_and(|code| USER_EXISTS_CODES.contains(&code))
+                || api_error
+                    .message()
+                    .is_some_and(|msg| msg.contains("already registered"));
+            if user_exists {
+                return AuthError::UserAlreadyExists(Some(api_error.clone()));
+            }
+            if api_error.error_code.as_deref() == Some(WEAK_PASSWORD_CODE) {
+                let reasons = body_reasons(resp_text);
+                return AuthError::WeakPassword {
+                    reasons,
+                    api_error: Some(api_error.clone()),
+                };
+            }
+            match api_error.error_code.as_deref() {
+                Some(REFRESH_TOKEN_REUSED_CODE) => {
+                    return AuthError::RefreshTokenReused(Some(api_error.clone()))
+                }
+                Some(REFRESH_TOKEN_NOT_FOUND_CODE) => {
+                    return AuthError::RefreshTokenNotFound(Some(api_error.clone()))
+                }
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// Stable, machine readable code identifying the error (e.g. `not_authorized`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            AuthError::NotAuthorized(_) => "not_authorized",
+            AuthError::InvalidParameters(_) => "invalid_parameters",
+            AuthError::Http => "http_error",
+            AuthError::Internal => "internal_error",
+            AuthError::NotFound(_) => "not_found",
+            AuthError::UserNotFound => "user_not_found",
+            AuthError::ConfirmationRequired => "confirmation_required",
+            AuthError::UserAlreadyExists(_) => "user_already_exists",
+            AuthError::ServiceRoleKeyRequired => "service_role_key_required",
+            AuthError::WeakPassword { .. } => "weak_password",
+            AuthError::RefreshTokenReused(_) => "refresh_token_reused",
+            AuthError::RefreshTokenNotFound(_) => "refresh_token_not_found",
+            AuthError::Timeout => "timeout",
+            AuthError::RateLimited { .. } => "rate_limited",
+            AuthError::GeneralError(_) => "general_error",
+        }
+    }
+
+    /// HTTP status code that best represents the error when passing it on to a caller
+    pub fn status(&self) -> u16 {
+        match self {
+            AuthError::NotAuthorized(_)
+            | AuthError::RefreshTokenReused(_)
+            | AuthError::RefreshTokenNotFound(_) => 401,
+            AuthError::InvalidParameters(_) => 400,
+            AuthError::ConfirmationRequired => 403,
+            AuthError::WeakPassword { .. } => 422,
+            AuthError::NotFound(_) | AuthError::UserNotFound => 404,
+            AuthError::UserAlreadyExists(_) => 409,
+            AuthError::Http => 502,
+            AuthError::Timeout => 504,
+            AuthError::RateLimited { .. } => 429,
+            AuthError::Internal
+            | AuthError::ServiceRoleKeyRequired
+            | AuthError::GeneralError(_) => 500,
+        }
+    }
+}
+
+/// Serializes the error as `{ "kind", "code", "message", "status" }`
+///
+/// The shape is stable so gateway services can embed auth failures in their own JSON
+/// error envelopes.
+impl Serialize for AuthError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("AuthError", 4)?;
+        state.serialize_field("kind", &self.kind().to_string())?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("status", &self.status())?;
+        state.end()
+    }
 }
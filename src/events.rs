@@ -0,0 +1,84 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing::Span;
+
+use crate::context::OperationContext;
+use crate::error::{AuthError, AuthErrorKind};
+use crate::AuthClient;
+
+/// Audit record emitted after every auth operation
+///
+/// See [`AuthClientBuilder::on_auth_event`](crate::AuthClientBuilder::on_auth_event).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthEvent {
+    /// Name of the operation, matching the method name (e.g. `signin_with_password`)
+    pub operation: &'static str,
+    /// Who the operation was about, with emails and phone numbers masked
+    pub subject: Option<String>,
+    /// Whether the operation succeeded
+    pub outcome: AuthEventOutcome,
+    /// Time spent in the operation
+    pub latency: Duration,
+    /// Context attached with [`AuthClient::with_context`]
+    pub context: OperationContext,
+}
+
+/// Outcome of an auth operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthEventOutcome {
+    /// The operation succeeded
+    Success,
+    /// The operation failed with an error of the given kind
+    Failure(AuthErrorKind),
+}
+
+/// Callback receiving auth events
+pub(crate) type AuthEventHook =
+    Arc<dyn Fn(AuthEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+impl AuthClient {
+    /// Runs `operation` and reports its outcome to the auth event hook, if configured
+    ///
+    /// The latency and, on failure, the error kind are recorded as the `latency_ms` and
+    /// `err` fields of the operation's span, and as metrics with the `metrics` feature.
+    /// Errors carry the client's [`OperationContext`] in their API error details.
+    /// The hook is awaited before the result is returned to the caller.
+    pub(crate) async fn observe<T>(
+        &self,
+        operation: &'static str,
+        subject: Option<String>,
+        fut: impl Future<Output = Result<T, AuthError>>,
+    ) -> Result<T, AuthError> {
+        let started = Instant::now();
+        let result = fut.await.map_err(|e| e.with_context(&self.context));
+        let latency = started.elapsed();
+
+        let span = Span::current();
+        span.record("latency_ms", latency.as_millis() as u64);
+        if let Err(e) = &result {
+            span.record("err", e.kind().to_string());
+        }
+        #[cfg(feature = "metrics")]
+        crate::operation_metrics::record_operation(operation, &result, latency);
+
+        if let Some(hook) = &self.auth_event_hook {
+            let outcome = match &result {
+                Ok(_) => AuthEventOutcome::Success,
+                Err(e) => AuthEventOutcome::Failure(e.kind()),
+            };
+            hook(AuthEvent {
+                operation,
+                subject,
+                outcome,
+                latency,
+                context: OperationContext::clone(&self.context),
+            })
+            .await;
+        }
+
+        result
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::{RequestBuilder, Response};
+use tracing::warn;
+
+use crate::AuthClient;
+
+/// How long an API URL is skipped after a connect error
+const FAILOVER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Path GoTrue is served under behind the Supabase API gateway
+pub(crate) const DEFAULT_AUTH_PATH: &str = "/auth/v1";
+
+/// `X-Client-Info` header identifying this crate, like the official SDKs send
+pub(crate) const DEFAULT_CLIENT_INFO: &str =
+    concat!("supabase-auth-redux/", env!("CARGO_PKG_VERSION"));
+
+/// Ordered list of auth base URLs with the health of each
+#[derive(Debug)]
+pub(crate) struct ApiUrls {
+    /// Base URLs in order of preference, the primary URL first, each joined with the auth
+    /// path
+    urls: Vec<String>,
+    /// Path prefix of the GoTrue endpoints, empty for a standalone GoTrue server
+    auth_path: String,
+    /// When each URL last failed to connect
+    failed_at: Mutex<Vec<Option<Instant>>>,
+}
+
+impl ApiUrls {
+    /// Joins each API URL with `auth_path`, e.g. `/auth/v1`, or `/` for a standalone GoTrue
+    /// server
+    pub(crate) fn new(urls: Vec<String>, auth_path: &str) -> Self {
+        let auth_path = match auth_path.trim_end_matches('/') {
+            "" => String::new(),
+            path if path.starts_with('/') => path.to_string(),
+            path => format!("/{}", path),
+        };
+        let urls: Vec<String> = urls
+            .iter()
+            .map(|url| format!("{}{}", url.trim_end_matches('/'), auth_path))
+            .collect();
+        let failed_at = Mutex::new(vec![None; urls.len()]);
+        Self {
+            urls,
+            auth_path,
+            failed_at,
+        }
+    }
+
+    /// Returns the primary auth base URL, used for URLs opened by browsers
+    pub(crate) fn primary(&self) -> &str {
+        &self.urls[0]
+    }
+
+    /// Returns the path prefix of the GoTrue endpoints
+    pub(crate) fn auth_path(&self) -> &str {
+        &self.auth_path
+    }
+
+    /// Returns the URL indices to try, healthy URLs first and each group in configured order
+    fn attempt_order(&self) -> Vec<usize> {
+        let failed_at = self.failed_at.lock().unwrap_or_else(|e| e.into_inner());
+        let (healthy, cooling_down): (Vec<usize>, Vec<usize>) = (0..self.urls.len())
+            .partition(|&i| failed_at[i].is_none_or(|at| at.elapsed() >= FAILOVER_COOLDOWN));
+        healthy.into_iter().chain(cooling_down).collect()
+    }
+
+    fn record(&self, index: usize, healthy: bool) {
+        let mut failed_at = self.failed_at.lock().unwrap_or_else(|e| e.into_inner());
+        failed_at[index] = (!healthy).then(Instant::now);
+    }
+}
+
+impl AuthClient {
+    /// Sends the request built by `request` for each configured API URL until one connects
+    ///
+    /// Only connect errors move on to the next URL; any other error, and any response
+    /// (including error statuses), is returned as is. A URL that failed to connect is tried
+    /// last until [`FAILOVER_COOLDOWN`] has passed. The configured request timeout applies
+    /// to each attempt, and every attempt carries the `X-Client-Info` header.
+    pub(crate) async fn send_with_failover(
+        &self,
+        request: impl Fn(&str) -> RequestBuilder,
+    ) -> Result<Response, reqwest::Error> {
+        let attempt_order = self.api_urls.attempt_order();
+        let mut last_error = None;
+
+        for (attempt, index) in attempt_order.iter().copied().enumerate() {
+            let api_url = &self.api_urls.urls[index];
+            let mut builder = request(api_url).header("X-Client-Info", &self.client_info);
+            if let Some(timeout) = self.request_timeout {
+                builder = builder.timeout(timeout);
+            }
+            match builder.send().await {
+                Ok(resp) => {
+                    self.api_urls.record(index, true);
+                    return Ok(resp);
+                }
+                Err(e) if e.is_connect() && attempt + 1 < attempt_order.len() => {
+                    warn!(api_url, "failed to connect, failing over: {}", e);
+                    self.api_urls.record(index, false);
+                    last_error = Some(e);
+                }
+                Err(e) => {
+                    if e.is_connect() {
+                        self.api_urls.record(index, false);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        // Only reached if no API URL is configured, which the constructors prevent
+        Err(last_error.expect("at least one API URL is configured"))
+    }
+}
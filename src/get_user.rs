@@ -1,12 +1,13 @@
 use log::error;
-use reqwest::StatusCode;
+use reqwest::{Method, StatusCode};
 use std::ops::Add;
-use tracing::{debug, instrument, trace_span, Instrument};
+use tracing::{debug, instrument};
 use uuid::Uuid;
 
 use crate::error::{AuthError, AuthErrorKind};
+use crate::models::raw::Raw;
 use crate::models::user::UserSchema;
-use crate::util::handle_response_code;
+use crate::util::{handle_response_code, parse_with_raw, Credentials, Endpoint};
 use crate::AuthClient;
 
 impl AuthClient {
@@ -38,55 +39,69 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[instrument(skip(self, auth_token), fields(auth_token = %self.redaction.token(auth_token), tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn get_user_by_token(&self, auth_token: &str) -> Result<UserSchema, AuthError> {
+        self.observe("get_user_by_token", None, async {
+            let resp_text = self.get_user_by_token_text(auth_token).await?;
+
+            let user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                Ok(user) => user,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            Ok(user)
+        })
+        .await
+    }
+
+    /// Retrieves user information using an authentication token, keeping the raw response
+    ///
+    /// Behaves like [`AuthClient::get_user_by_token`], but also returns the raw JSON response
+    /// body so fields not modelled by `UserSchema` remain accessible.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let user = client.get_user_by_token_with_raw("user-access-token").await?;
+    /// println!("is_anonymous: {:?}", user.raw.get("is_anonymous"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, auth_token), fields(auth_token = %self.redaction.token(auth_token), tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn get_user_by_token_with_raw(
+        &self,
+        auth_token: &str,
+    ) -> Result<Raw<UserSchema>, AuthError> {
+        self.observe("get_user_by_token", None, async {
+            let resp_text = self.get_user_by_token_text(auth_token).await?;
+            parse_with_raw(&resp_text)
+        })
+        .await
+    }
+
+    async fn get_user_by_token_text(&self, auth_token: &str) -> Result<String, AuthError> {
         if auth_token.is_empty() {
             error!("empty token");
-            return Err(AuthError::InvalidParameters);
+            return Err(AuthError::InvalidParameters(None));
         }
 
-        let resp = match self
-            .http_client
-            .get(format!("{}/auth/v1/{}", self.supabase_api_url, "user"))
-            .bearer_auth(auth_token)
-            .header("apiKey", &self.supabase_anon_key)
-            .send()
-            .instrument(trace_span!("gotrue get user"))
+        self.execute_text(Endpoint::new(Method::GET, "user", Credentials::User(auth_token)).retry())
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                debug!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result?;
-
-        let user = match serde_json::from_str::<UserSchema>(&resp_text) {
-            Ok(user) => user,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-
-        Ok(user)
     }
 
     /// Retrieves user information by user ID
     ///
-    /// This method fetches a user's information directly from the database using their UUID.
-    /// Note: This requires appropriate permissions and may need a service role key depending
-    /// on your Row Level Security policies.
+    /// This method fetches a user through the admin API (`GET /auth/v1/admin/users/{id}`),
+    /// which works on hosted projects regardless of which schemas PostgREST exposes.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
     ///
     /// # Arguments
     ///
@@ -98,7 +113,8 @@ impl AuthClient {
     ///
     /// # Errors
     ///
-    /// Returns `AuthError::Http` if the database query fails.
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::Http` if the API request fails.
     ///
     /// # Example
     ///
@@ -106,7 +122,11 @@ impl AuthClient {
     /// # use supabase_auth_redux::AuthClient;
     /// # use uuid::Uuid;
     /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
-    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .service_role_key("your-service-role-key")
+    ///     .build()?;
     ///
     /// let user_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
     /// if let Some(user) = client.get_user_by_id(user_id).await? {
@@ -115,81 +135,125 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[instrument(skip(self), fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<UserSchema>, AuthError> {
-        let query_result = self
-            .postgrest_client
-            .from("users")
-            .auth(&self.supabase_anon_key)
-            .eq("id", user_id.to_string())
-            .select("*")
-            .execute()
-            .await;
-        let query_response = match query_result {
-            Ok(query_response) => query_response,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
+        self.observe("get_user_by_id", Some(user_id.to_string()), async {
+            let endpoint = Endpoint::new(
+                Method::GET,
+                format!("admin/users/{}", user_id),
+                Credentials::ServiceRole,
+            )
+            .retry()
+            .not_found();
+            match self.execute::<_, UserSchema>(endpoint).await {
+                Ok(user) => Ok(Some(user)),
+                Err(AuthError::NotFound(None)) => Ok(None),
+                Err(e) => Err(e),
             }
-        };
-        if query_response.status().as_u16() == StatusCode::NOT_FOUND.as_u16() {
-            return Ok(None);
-        }
+        })
+        .await
+    }
 
-        let reqwuest_http_status_result = StatusCode::from_u16(query_response.status().as_u16());
-        let Ok(eqwuest_http_status) = reqwuest_http_status_result else {
-            log::error!(
-                "could not covert http status: {:?}",
-                reqwuest_http_status_result.unwrap_err()
-            );
-            return Err(AuthError::Http);
-        };
-        let handle_response_code_result = handle_response_code(eqwuest_http_status).await;
-        let body_text = match query_response.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!(body = body_text);
-        if let Err(e) = handle_response_code_result {
-            if e.kind() == AuthErrorKind::NotFound {
-                return Ok(None);
-            }
-            handle_response_code_result?
-        }
+    /// Retrieves user information by user ID from the `auth.users` table
+    ///
+    /// This is how [`AuthClient::get_user_by_id`] used to look users up: it queries the
+    /// table through PostgREST with the anon key. It only works where the `auth` schema is
+    /// exposed to PostgREST and row level security allows the read, which is not the case
+    /// on hosted projects. Prefer [`AuthClient::get_user_by_id`].
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(user))` if the user exists, `Ok(None)` if not found.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Http` if the database query fails.
+    #[instrument(skip(self), fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn get_user_by_id_postgrest(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<UserSchema>, AuthError> {
+        self.observe(
+            "get_user_by_id_postgrest",
+            Some(user_id.to_string()),
+            async {
+                let query_result = self
+                    .postgrest_client
+                    .from("users")
+                    .auth(&self.supabase_anon_key)
+                    .eq("id", user_id.to_string())
+                    .select("*")
+                    .execute()
+                    .await;
+                let query_response = match query_result {
+                    Ok(query_response) => query_response,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                if query_response.status().as_u16() == StatusCode::NOT_FOUND.as_u16() {
+                    return Ok(None);
+                }
 
-        let users = match serde_json::from_str::<Vec<UserSchema>>(&body_text) {
-            Ok(users) => users,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
+                let reqwuest_http_status_result =
+                    StatusCode::from_u16(query_response.status().as_u16());
+                let Ok(eqwuest_http_status) = reqwuest_http_status_result else {
+                    log::error!(
+                        "could not covert http status: {:?}",
+                        reqwuest_http_status_result.unwrap_err()
+                    );
+                    return Err(AuthError::Http);
+                };
+                let handle_response_code_result = handle_response_code(eqwuest_http_status).await;
+                let body_text = match query_response.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!(body = self.redaction.response(&body_text));
+                if let Err(e) = handle_response_code_result {
+                    if e.kind() == AuthErrorKind::NotFound {
+                        return Ok(None);
+                    }
+                    return Err(e);
+                }
 
-        if users.iter().len() > 1 {
-            let user_ids_stringify = users
-                .iter()
-                .map(|user| user.id)
-                .fold(String::new(), |mut acc, user_id| {
-                    if acc.is_empty() {
-                        let s = format!("[ {}", user_id);
-                        acc = acc.add(&s);
-                    } else {
-                        let s = format!(", {}", user_id);
-                        acc = acc.add(&s);
+                let users = match serde_json::from_str::<Vec<UserSchema>>(&body_text) {
+                    Ok(users) => users,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
                     }
-                    acc
-                })
-                .add(" ]");
-            debug!(
-                user_ids = user_ids_stringify,
-                "multiple users returned for single user_id"
-            );
-            return Err(AuthError::Internal);
-        }
+                };
+
+                if users.iter().len() > 1 {
+                    let user_ids_stringify = users
+                        .iter()
+                        .map(|user| user.id)
+                        .fold(String::new(), |mut acc, user_id| {
+                            if acc.is_empty() {
+                                let s = format!("[ {}", user_id);
+                                acc = acc.add(&s);
+                            } else {
+                                let s = format!(", {}", user_id);
+                                acc = acc.add(&s);
+                            }
+                            acc
+                        })
+                        .add(" ]");
+                    debug!(
+                        user_ids = user_ids_stringify,
+                        "multiple users returned for single user_id"
+                    );
+                    return Err(AuthError::Internal);
+                }
 
-        Ok(users.first().cloned())
+                Ok(users.first().cloned())
+            },
+        )
+        .await
     }
 }
@@ -0,0 +1,89 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use tracing::error;
+
+use crate::error::AuthError;
+use crate::AuthClient;
+
+/// Header carrying the project API key
+const API_KEY: HeaderName = HeaderName::from_static("apikey");
+
+impl AuthClient {
+    /// Returns the headers authenticating a request with the service role key
+    ///
+    /// The map holds `apikey` and `Authorization: Bearer` set to the service role key, the
+    /// same credentials the admin operations of this client send. Use it for hand-rolled
+    /// calls to PostgREST, Storage or Edge Functions that need to bypass row level security.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the key is not a valid header value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(admin_client: AuthClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let resp = reqwest::Client::new()
+    ///     .post("https://your-project.supabase.co/functions/v1/nightly-report")
+    ///     .headers(admin_client.service_role_headers()?)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn service_role_headers(&self) -> Result<HeaderMap, AuthError> {
+        let service_role_key = self
+            .supabase_service_role_key
+            .as_ref()
+            .ok_or(AuthError::ServiceRoleKeyRequired)?;
+        credential_headers(service_role_key, service_role_key)
+    }
+
+    /// Returns the headers authenticating a request as the user owning `access_token`
+    ///
+    /// The map holds `apikey` set to the anon key and `Authorization: Bearer` set to the
+    /// user's access token, so row level security applies to the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the access token is not a valid header value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// let resp = reqwest::Client::new()
+    ///     .get("https://your-project.supabase.co/storage/v1/bucket")
+    ///     .headers(client.session_headers(access_token)?)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn session_headers(&self, access_token: &str) -> Result<HeaderMap, AuthError> {
+        credential_headers(&self.supabase_anon_key, access_token)
+    }
+}
+
+fn credential_headers(api_key: &str, bearer_token: &str) -> Result<HeaderMap, AuthError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(API_KEY, sensitive_header_value(api_key.to_string())?);
+    headers.insert(AUTHORIZATION, bearer_header_value(bearer_token)?);
+    Ok(headers)
+}
+
+/// Returns `Bearer <token>` as a sensitive header value
+pub(crate) fn bearer_header_value(token: &str) -> Result<HeaderValue, AuthError> {
+    sensitive_header_value(format!("Bearer {}", token))
+}
+
+fn sensitive_header_value(value: String) -> Result<HeaderValue, AuthError> {
+    let mut value = HeaderValue::try_from(value).map_err(|e| {
+        error!("{}", e);
+        AuthError::InvalidParameters(None)
+    })?;
+    value.set_sensitive(true);
+    Ok(value)
+}
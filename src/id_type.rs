@@ -0,0 +1,183 @@
+//! Validation of email addresses and phone numbers used as identifiers
+
+use crate::error::{ApiError, AuthError};
+use crate::IdType;
+
+/// Maximum length of an email address (RFC 5321)
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Maximum length of the local part of an email address (RFC 5321)
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// Minimum number of digits accepted in a phone number
+const MIN_PHONE_DIGITS: usize = 7;
+
+/// Maximum number of digits of an E.164 phone number
+const MAX_PHONE_DIGITS: usize = 15;
+
+fn invalid(message: String) -> AuthError {
+    AuthError::InvalidParameters(Some(Box::new(ApiError::validation(message))))
+}
+
+/// Checks the syntax of a trimmed email address
+fn validate_email(email: &str) -> Result<(), AuthError> {
+    if email.is_empty() {
+        return Err(invalid("email address is empty".to_string()));
+    }
+    if email.len() > MAX_EMAIL_LENGTH {
+        return Err(invalid(format!(
+            "email address is longer than {} characters",
+            MAX_EMAIL_LENGTH
+        )));
+    }
+    if email.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid("email address contains whitespace".to_string()));
+    }
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return Err(invalid("email address is missing an @".to_string()));
+    };
+    if local.is_empty() || local.len() > MAX_LOCAL_PART_LENGTH {
+        return Err(invalid(format!(
+            "local part of the email address must be 1 to {} characters",
+            MAX_LOCAL_PART_LENGTH
+        )));
+    }
+    if local.contains('@') {
+        return Err(invalid(
+            "email address contains more than one @".to_string(),
+        ));
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err(invalid(
+            "local part of the email address has misplaced dots".to_string(),
+        ));
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return Err(invalid(format!("email domain {:?} has no dot", domain)));
+    }
+    for label in labels {
+        let valid = !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-');
+        if !valid {
+            return Err(invalid(format!("email domain {:?} is invalid", domain)));
+        }
+    }
+    Ok(())
+}
+
+/// Normalizes a phone number to E.164 (`+` followed by the digits)
+///
+/// Spaces, dashes, dots and parentheses are removed and a `00` international prefix is
+/// replaced with `+`. Numbers without a prefix are assumed to start with the country code,
+/// as GoTrue does.
+pub(crate) fn normalize_phone(phone_number: &str) -> Result<String, AuthError> {
+    let compact: String = phone_number
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    let digits = compact
+        .strip_prefix('+')
+        .or_else(|| compact.strip_prefix("00"))
+        .unwrap_or(&compact);
+    if digits.is_empty() {
+        return Err(invalid("phone number is empty".to_string()));
+    }
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid(
+            "phone number may only contain digits after the leading +".to_string(),
+        ));
+    }
+    if digits.starts_with('0') {
+        return Err(invalid(
+            "phone number must start with a country code".to_string(),
+        ));
+    }
+    if !(MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits.len()) {
+        return Err(invalid(format!(
+            "phone number must have {} to {} digits",
+            MIN_PHONE_DIGITS, MAX_PHONE_DIGITS
+        )));
+    }
+    Ok(format!("+{}", digits))
+}
+
+impl IdType {
+    /// Creates an email identifier after checking the address syntax
+    ///
+    /// Surrounding whitespace is trimmed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` describing the problem if the address is
+    /// malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::IdType;
+    ///
+    /// assert!(IdType::email("user@example.com").is_ok());
+    /// assert!(IdType::email("user@localhost").is_err());
+    /// ```
+    pub fn email(email: &str) -> Result<IdType, AuthError> {
+        let email = email.trim();
+        validate_email(email)?;
+        Ok(IdType::Email(email.to_string()))
+    }
+
+    /// Creates a phone number identifier normalized to E.164
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` describing the problem if the number is not a
+    /// valid international phone number.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::IdType;
+    ///
+    /// assert_eq!(
+    ///     IdType::phone("+1 (415) 555-0100").unwrap(),
+    ///     IdType::PhoneNumber("+14155550100".to_string())
+    /// );
+    /// assert!(IdType::phone("call me").is_err());
+    /// ```
+    pub fn phone(phone_number: &str) -> Result<IdType, AuthError> {
+        normalize_phone(phone_number).map(IdType::PhoneNumber)
+    }
+
+    /// Checks that the identifier is a well-formed email address or E.164 phone number
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` describing the problem if it is not.
+    pub fn validate(&self) -> Result<(), AuthError> {
+        match self {
+            IdType::Email(email) => validate_email(email),
+            IdType::PhoneNumber(phone_number) => {
+                if normalize_phone(phone_number)? != *phone_number {
+                    return Err(invalid("phone number is not in E.164 format".to_string()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parses an email address (anything containing an `@`) or a phone number
+impl TryFrom<&str> for IdType {
+    type Error = AuthError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.contains('@') {
+            IdType::email(value)
+        } else {
+            IdType::phone(value)
+        }
+    }
+}
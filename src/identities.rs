@@ -0,0 +1,244 @@
+use serde::Deserialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::oauth::{LinkIdentityOptions, OAuthAuthorizeUrl};
+use crate::models::provider::Provider;
+use crate::models::user::IdentitySchema;
+use crate::pkce::{generate_code_verifier, CodeChallenge};
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+#[derive(Debug, Deserialize)]
+struct LinkIdentityResponse {
+    url: String,
+}
+
+impl AuthClient {
+    /// Returns the identities linked to the user an access token belongs to
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`AuthClient::get_user_by_token`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// for identity in client.get_user_identities(access_token).await? {
+    ///     println!("{} ({:?})", identity.provider, identity.email);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_user_identities(
+        &self,
+        access_token: &str,
+    ) -> Result<Vec<IdentitySchema>, AuthError> {
+        let user = self.get_user_by_token(access_token).await?;
+        Ok(user.identities.unwrap_or_default())
+    }
+
+    /// Starts linking an OAuth identity to the user an access token belongs to
+    ///
+    /// Works like [`AuthClient::get_oauth_authorize_url`]: redirect the user's browser to
+    /// the returned URL and keep the returned code verifier. After the provider
+    /// authenticates the user, GoTrue links the identity and redirects with a `code`, which
+    /// [`AuthClient::exchange_code_for_session`] exchanges for a session. Manual linking
+    /// must be enabled on the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the signed in user
+    /// * `provider` - The provider of the identity to link
+    /// * `options` - Redirect URL, additional scopes and provider query parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the provider is `email`, `phone` or
+    /// `anonymous`, or the server rejects the request.
+    /// Returns `AuthError::NotAuthorized` if the access token is invalid or manual linking
+    /// is disabled.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::oauth::LinkIdentityOptions;
+    /// use supabase_auth_redux::models::provider::Provider;
+    ///
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let authorize = client
+    ///     .link_identity(
+    ///         access_token,
+    ///         Provider::Github,
+    ///         LinkIdentityOptions::new().redirect_to("https://app.example.com/settings"),
+    ///     )
+    ///     .await?;
+    /// // Store `authorize.code_verifier`, then redirect the browser to `authorize.url`
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn link_identity(
+        &self,
+        access_token: &str,
+        provider: Provider,
+        options: LinkIdentityOptions,
+    ) -> Result<OAuthAuthorizeUrl, AuthError> {
+        self.observe("link_identity", Some(provider.to_string()), async {
+            if matches!(
+                provider,
+                Provider::Email | Provider::Phone | Provider::Anonymous
+            ) {
+                error!(%provider, "provider does not support oauth");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let code_verifier = generate_code_verifier();
+            let challenge = CodeChallenge::from_verifier(&code_verifier);
+            let mut query = vec![
+                ("provider", provider.as_str().to_string()),
+                ("skip_http_redirect", "true".to_string()),
+                ("code_challenge", challenge.code_challenge),
+                ("code_challenge_method", challenge.code_challenge_method),
+            ];
+            if let Some(redirect_to) = self.redirect_url(options.redirect_to.as_deref()) {
+                query.push(("redirect_to", redirect_to.to_string()));
+            }
+            if !options.scopes.is_empty() {
+                query.push(("scopes", options.scopes.join(" ")));
+            }
+            query.extend(
+                options
+                    .query_params
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.clone())),
+            );
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .get(format!("{}/user/identities/authorize", api_url))
+                        .query(&query)
+                        .bearer_auth(access_token)
+                        .header("apiKey", &self.supabase_anon_key)
+                })
+                .instrument(trace_span!("gotrue link identity"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let link = match serde_json::from_str::<LinkIdentityResponse>(&resp_text) {
+                Ok(link) => link,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+
+            Ok(OAuthAuthorizeUrl {
+                url: link.url,
+                code_verifier,
+            })
+        })
+        .await
+    }
+
+    /// Unlinks an identity from the user an access token belongs to
+    ///
+    /// Users must keep at least one identity, and manual linking must be enabled on the
+    /// project.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token of the signed in user
+    /// * `identity_id` - The [`IdentitySchema::identity_id`] of the identity to unlink
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the identity is the user's only one.
+    /// Returns `AuthError::NotAuthorized` if the access token is invalid or manual linking
+    /// is disabled.
+    /// Returns `AuthError::NotFound` if the user has no such identity.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::provider::Provider;
+    ///
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let identities = client.get_user_identities(access_token).await?;
+    /// if let Some(github) = identities.iter().find(|identity| identity.provider == Provider::Github) {
+    ///     if let Some(identity_id) = github.identity_id {
+    ///         client.unlink_identity(access_token, identity_id).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn unlink_identity(
+        &self,
+        access_token: &str,
+        identity_id: Uuid,
+    ) -> Result<(), AuthError> {
+        self.observe("unlink_identity", None, async {
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .delete(format!("{}/user/identities/{}", api_url, identity_id))
+                        .bearer_auth(access_token)
+                        .header("apiKey", &self.supabase_anon_key)
+                })
+                .instrument(trace_span!("gotrue unlink identity"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = match resp.status() {
+                reqwest::StatusCode::NOT_FOUND => Err(AuthError::NotFound(None)),
+                _ => handle_response(&resp).await,
+            };
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            info!(identity_id = identity_id.to_string(), "unlinked identity");
+            Ok(())
+        })
+        .await
+    }
+}
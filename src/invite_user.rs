@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::user::UserSchema;
+use crate::util::{error_with_body, handle_response, mask_identifier};
+use crate::AuthClient;
+
+#[derive(Debug, Serialize)]
+struct InviteRequest<'a> {
+    email: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl AuthClient {
+    /// Invites a user by email
+    ///
+    /// Creates the user and sends an invitation email with a link that signs them in. The
+    /// user sets a password afterwards, e.g. with [`AuthClient::update_user`].
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - The email address to invite
+    /// * `data` - Metadata stored on the invited user
+    /// * `redirect_to` - URL the invitation link redirects to; must be in the project's
+    ///   allow list. The client's default redirect URL, or else the project's site URL, is
+    ///   used when `None`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the invited `UserSchema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if no service role key is configured.
+    /// Returns `AuthError::InvalidParameters` if the email is empty.
+    /// Returns `AuthError::UserAlreadyExists` if the email is already registered.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(admin_client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = admin_client
+    ///     .invite_user_by_email(
+    ///         "new-hire@example.com",
+    ///         Some(HashMap::from([("team".to_string(), "support".into())])),
+    ///         Some("https://app.example.com/welcome"),
+    ///     )
+    ///     .await?;
+    /// println!("Invited {}", user.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn invite_user_by_email(
+        &self,
+        email: &str,
+        data: Option<HashMap<String, serde_json::Value>>,
+        redirect_to: Option<&str>,
+    ) -> Result<UserSchema, AuthError> {
+        let email = self.normalize_email(email.to_string());
+        self.observe(
+            "invite_user_by_email",
+            Some(mask_identifier(&email)),
+            async {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_ref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+                if email.is_empty() {
+                    error!("empty email");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let body = InviteRequest {
+                    email: &email,
+                    data,
+                };
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        let mut request = self
+                            .http_client
+                            .post(format!("{}/invite", api_url))
+                            .json(&body)
+                            .bearer_auth(service_role_key)
+                            .header("apiKey", service_role_key);
+                        if let Some(redirect_to) = self.redirect_url(redirect_to) {
+                            request = request.query(&[("redirect_to", redirect_to)]);
+                        }
+                        request
+                    })
+                    .instrument(trace_span!("gotrue invite"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = handle_response(&resp).await;
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!("resp_text: {}", self.redaction.response(&resp_text));
+                resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+                let user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                    Ok(user) => user,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Internal);
+                    }
+                };
+                info!(user_id = user.id.to_string(), "invited user");
+
+                Ok(user)
+            },
+        )
+        .await
+    }
+}
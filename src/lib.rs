@@ -39,14 +39,36 @@
 #![warn(missing_docs)]
 
 use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
 
 use postgrest::Postgrest;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-pub use error::AuthError;
+use crate::email_normalization::EmailNormalization;
+use crate::events::AuthEventHook;
+use crate::failover::{ApiUrls, DEFAULT_AUTH_PATH, DEFAULT_CLIENT_INFO};
+use crate::jwt::JwtVerifier;
+use crate::password_policy::PasswordPolicy;
+use crate::refresh_flight::RefreshFlights;
+use crate::retry::RetryPolicy;
+
+pub use context::OperationContext;
+pub use error::{ApiError, AuthError, AuthErrorKind};
+pub use events::{AuthEvent, AuthEventOutcome};
+pub use models::raw::Raw;
+pub use models::session::Session;
 pub use models::token::TokenResponse;
 pub use models::user::UserSchema as User;
+pub use redaction::RedactionPolicy;
+pub use session_from_url::{parse_session_from_url, UrlSession};
+pub use session_manager::{CredentialProvider, SessionManager, TokenReuseEvent};
+pub use session_store::{FileSessionStore, MemorySessionStore, SessionStore, DEFAULT_IDLE_TIMEOUT};
+pub use supabase_auth::SupabaseAuth;
+pub use util::mask_token;
+pub use wait_for_confirmation::{ConfirmationTarget, PollOptions};
 
 // Re-export for backward compatibility
 #[allow(unused)]
@@ -56,15 +78,77 @@ pub use models::user::UserSchema as User;
 )]
 pub use GoTrueErrorResponse as Error;
 
+#[cfg(feature = "actix")]
+pub mod actix;
+mod admin_auth_stats;
+mod admin_create_user;
+mod admin_factors;
+mod admin_generate_link;
+mod admin_list_users;
+mod admin_update_factor;
+mod admin_update_user;
+mod admin_user_sessions;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builders;
+mod context;
+#[cfg(feature = "cookie")]
+pub mod cookie;
 mod delete_user;
+mod email_change;
+pub mod email_normalization;
 mod error;
+mod events;
+mod failover;
 mod get_user;
+mod headers;
+mod id_type;
+mod identities;
+mod invite_user;
+pub mod jwt;
+#[cfg(feature = "local-dev")]
+pub mod local_dev;
 mod logout;
+mod mfa;
+pub mod migrate;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod models;
+#[cfg(feature = "metrics")]
+pub mod operation_metrics;
+pub mod password_policy;
+mod phone_change;
+pub mod pkce;
+mod project;
+mod reauthenticate;
+pub mod redaction;
+mod refresh_flight;
 mod refresh_token;
+mod request;
+mod reset_password;
+pub mod retry;
+mod session_from_url;
+mod session_manager;
+mod session_store;
+mod settings;
+mod signin_with_id_token;
+mod signin_with_oauth;
+mod signin_with_otp;
 mod signin_with_password;
 mod signup;
+mod supabase_auth;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
+mod update_user;
 mod util;
+mod verify_otp;
+mod wait_for_confirmation;
 
 /// The main authentication client for interacting with Supabase Auth API
 ///
@@ -74,14 +158,37 @@ mod util;
 pub struct AuthClient {
     /// HTTP client for making API requests
     http_client: reqwest::Client,
-    /// Base URL of the Supabase API (e.g., `https://your-project.supabase.co`)
-    supabase_api_url: String,
+    /// Auth base URLs (e.g., `https://your-project.supabase.co/auth/v1`), tried in order on
+    /// connect errors
+    api_urls: Arc<ApiUrls>,
     /// Anonymous key for public API access
     supabase_anon_key: String,
     /// Optional service role key for admin operations
     supabase_service_role_key: Option<String>,
     /// PostgREST client for direct database queries
     postgrest_client: Postgrest,
+    /// Caller supplied context recorded on operation spans
+    context: Arc<OperationContext>,
+    /// Password policy checked before passwords are sent to the server
+    password_policy: Option<PasswordPolicy>,
+    /// Normalization applied to email addresses before they are sent to the server
+    email_normalization: Option<EmailNormalization>,
+    /// Verifier for access tokens checked without contacting the server
+    jwt_verifier: Option<Arc<JwtVerifier>>,
+    /// Callback notified after every auth operation
+    auth_event_hook: Option<AuthEventHook>,
+    /// Total time allowed for each request to the auth API
+    request_timeout: Option<Duration>,
+    /// Retries of transient failures of idempotent operations
+    retry_policy: Option<RetryPolicy>,
+    /// URL email links and OAuth sign-ins redirect to when a request sets none
+    default_redirect_to: Option<String>,
+    /// How much of tokens and user identifiers is logged
+    redaction: RedactionPolicy,
+    /// Refresh requests in flight, shared by concurrent refreshes of the same token
+    refresh_flights: Arc<RefreshFlights>,
+    /// `X-Client-Info` header identifying the client in the project's logs
+    client_info: String,
 }
 
 impl Debug for AuthClient {
@@ -108,20 +215,31 @@ impl AuthClient {
     /// ```
     pub fn new(api_url: &str, anon_key: &str) -> Result<Self, AuthError> {
         if api_url.is_empty() {
-            return Err(AuthError::InvalidParameters);
+            return Err(AuthError::InvalidParameters(None));
         }
         if anon_key.is_empty() {
-            return Err(AuthError::InvalidParameters);
+            return Err(AuthError::InvalidParameters(None));
         }
 
         Ok(Self {
             http_client: reqwest::Client::new(),
-            supabase_api_url: api_url.to_owned(),
+            api_urls: Arc::new(ApiUrls::new(vec![api_url.to_owned()], DEFAULT_AUTH_PATH)),
             supabase_anon_key: anon_key.to_owned(),
             supabase_service_role_key: None,
             postgrest_client: Postgrest::new(format!("{}/rest/v1/", api_url.to_owned()))
                 .schema("auth")
                 .insert_header("apikey", anon_key),
+            context: Arc::default(),
+            password_policy: None,
+            email_normalization: None,
+            jwt_verifier: None,
+            auth_event_hook: None,
+            request_timeout: None,
+            retry_policy: None,
+            default_redirect_to: None,
+            redaction: RedactionPolicy::default(),
+            refresh_flights: Arc::default(),
+            client_info: DEFAULT_CLIENT_INFO.to_string(),
         })
     }
 
@@ -142,6 +260,11 @@ impl AuthClient {
     pub fn builder() -> AuthClientBuilder {
         AuthClientBuilder::default()
     }
+
+    /// Returns `redirect_to`, or the configured default redirect URL if it is `None`
+    pub(crate) fn redirect_url<'a>(&'a self, redirect_to: Option<&'a str>) -> Option<&'a str> {
+        redirect_to.or(self.default_redirect_to.as_deref())
+    }
 }
 
 /// Builder for constructing an AuthClient with custom configuration
@@ -153,6 +276,32 @@ pub struct AuthClientBuilder {
     anon_key: Option<String>,
     /// Optional service role key for admin operations
     service_role_key: Option<String>,
+    /// Auth base URLs tried when the API URL cannot be reached
+    fallback_api_urls: Vec<String>,
+    /// Optional path prefix of the GoTrue endpoints
+    auth_path: Option<String>,
+    /// Optional client-side password policy
+    password_policy: Option<PasswordPolicy>,
+    /// Optional email address normalization
+    email_normalization: Option<EmailNormalization>,
+    /// Optional verifier for access tokens
+    jwt_verifier: Option<JwtVerifier>,
+    /// Optional callback notified after every auth operation
+    auth_event_hook: Option<AuthEventHook>,
+    /// Optional preconfigured HTTP client
+    http_client: Option<reqwest::Client>,
+    /// Optional total timeout of each request
+    timeout: Option<Duration>,
+    /// Optional timeout for connecting to the auth API
+    connect_timeout: Option<Duration>,
+    /// Optional retry policy for idempotent operations
+    retry_policy: Option<RetryPolicy>,
+    /// Optional default redirect URL of email links and OAuth sign-ins
+    redirect_to: Option<String>,
+    /// Redaction of logged tokens and user identifiers
+    redaction: RedactionPolicy,
+    /// Optional `X-Client-Info` header value
+    client_info: Option<String>,
 }
 
 impl AuthClientBuilder {
@@ -174,32 +323,309 @@ impl AuthClientBuilder {
         self
     }
 
+    /// Adds an auth base URL used when the API URL cannot be reached
+    ///
+    /// Fallback URLs are tried in the order they were added whenever connecting to the
+    /// preferred URL fails, e.g. to fall back from a custom auth domain to the
+    /// `supabase.co` URL of the project. A URL that failed to connect is only retried
+    /// first again after a cooldown of 30 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://auth.example.com")
+    ///     .fallback_api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn fallback_api_url(mut self, url: &str) -> Self {
+        self.fallback_api_urls.push(url.to_string());
+        self
+    }
+
+    /// Sets the path the GoTrue endpoints are served under, `/auth/v1` by default
+    ///
+    /// Supabase serves GoTrue below `/auth/v1` of the project URL. Self-hosted setups
+    /// routing it elsewhere set their prefix; for a standalone GoTrue server without the API
+    /// gateway, point the API URL at GoTrue's port and set an empty path. The path applies
+    /// to the fallback URLs as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("http://localhost:9999")
+    ///     .anon_key("your-anon-key")
+    ///     .auth_path("")
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn auth_path(mut self, path: &str) -> Self {
+        self.auth_path = Some(path.to_string());
+        self
+    }
+
+    /// Sets a password policy checked before passwords are sent to the server
+    ///
+    /// Applies to signup, [`AuthClient::update_user`] and admin user creation and updates.
+    /// Passwords violating the policy are rejected with `AuthError::WeakPassword` without
+    /// making a request. Use [`AuthClient::fetch_password_policy`] to read the policy from
+    /// servers reporting it.
+    pub fn password_policy(mut self, policy: PasswordPolicy) -> Self {
+        self.password_policy = Some(policy);
+        self
+    }
+
+    /// Normalizes email addresses before they are sent to the server
+    ///
+    /// Applies to signup, sign-in (password and OTP) and admin user creation, so
+    /// addresses differing only in case or surrounding whitespace map to the same account.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::email_normalization::EmailNormalization;
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .email_normalization(EmailNormalization::new().fold_gmail_dots(true))
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn email_normalization(mut self, normalization: EmailNormalization) -> Self {
+        self.email_normalization = Some(normalization);
+        self
+    }
+
+    /// Sets the `X-Client-Info` header sent with every request to the auth API
+    ///
+    /// Defaults to `supabase-auth-redux/<version>`, identifying this crate in the project's
+    /// logs like the official Supabase SDKs identify themselves. Applications embedding the
+    /// client may set their own name and version instead.
+    pub fn client_info(mut self, client_info: &str) -> Self {
+        self.client_info = Some(client_info.to_string());
+        self
+    }
+
+    /// Sets the HTTP client used for requests to the auth API
+    ///
+    /// Use a preconfigured client to set proxies, custom TLS roots, timeouts or connection
+    /// pool limits. A client with default settings is used otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .timeout(Duration::from_secs(10))
+    ///     .pool_max_idle_per_host(4)
+    ///     .build()
+    ///     .expect("Failed to create http client");
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .http_client(http_client)
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the total time allowed for each request to the auth API
+    ///
+    /// The timeout covers connecting, sending the request and reading the response. A
+    /// request that times out fails with `AuthError::Http`. Requests have no timeout by
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the time allowed for connecting to the auth API
+    ///
+    /// A connect timeout counts as a connect error, so the next
+    /// [fallback URL](AuthClientBuilder::fallback_api_url) is tried. Cannot be combined
+    /// with [`AuthClientBuilder::http_client`]; set the connect timeout on that client
+    /// instead.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Retries transient failures of idempotent operations
+    ///
+    /// See the [`retry`] module for the operations covered.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Sets the URL email links and OAuth sign-ins redirect to when a request sets none
+    ///
+    /// Applies to signup confirmation, magic link, invite, recovery and email change
+    /// emails, generated links and OAuth sign-ins and identity linking. Without it, GoTrue
+    /// redirects to the project's site URL, so set it per environment (e.g. to a staging
+    /// or preview URL). The URL must be in the project's redirect allow list.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .redirect_to("https://staging.example.com/auth/callback")
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Sets how much of tokens, email addresses and phone numbers is logged
+    ///
+    /// Defaults to [`RedactionPolicy::Partial`]. Use [`RedactionPolicy::Full`] where no
+    /// part of a secret or identifier may reach the logs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::{AuthClient, RedactionPolicy};
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .redaction(RedactionPolicy::Full)
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.redaction = policy;
+        self
+    }
+
+    /// Sets the verifier used by [`AuthClient::verify_access_token`]
+    pub fn jwt_verifier(mut self, verifier: JwtVerifier) -> Self {
+        self.jwt_verifier = Some(verifier);
+        self
+    }
+
+    /// Registers an async callback notified after every auth operation
+    ///
+    /// The callback receives an [`AuthEvent`] with the operation name, the masked subject,
+    /// the outcome and the latency, which makes it a single place to ship an audit trail.
+    /// It is awaited before the operation returns, so long-running work should be handed
+    /// off (e.g. to a channel).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .on_auth_event(|event| async move {
+    ///         println!("{} {:?} in {:?}", event.operation, event.outcome, event.latency);
+    ///     })
+    ///     .build()
+    ///     .expect("Failed to create auth client");
+    /// ```
+    pub fn on_auth_event<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(AuthEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.auth_event_hook = Some(Arc::new(move |event| Box::pin(callback(event))));
+        self
+    }
+
     /// Builds the AuthClient with the configured settings
     ///
     /// # Errors
     ///
-    /// Returns `AuthError::InvalidParameters` if required fields are missing
+    /// Returns `AuthError::InvalidParameters` if required fields are missing, or a connect
+    /// timeout is combined with a preconfigured HTTP client
     pub fn build(self) -> Result<AuthClient, AuthError> {
-        let api_url = self.api_url.ok_or(AuthError::InvalidParameters)?;
-        let anon_key = self.anon_key.ok_or(AuthError::InvalidParameters)?;
+        let api_url = self.api_url.ok_or(AuthError::InvalidParameters(None))?;
+        let anon_key = self.anon_key.ok_or(AuthError::InvalidParameters(None))?;
+
+        let http_client = match (self.http_client, self.connect_timeout) {
+            (Some(_), Some(_)) => return Err(AuthError::InvalidParameters(None)),
+            (Some(http_client), None) => http_client,
+            (None, Some(connect_timeout)) => reqwest::Client::builder()
+                .connect_timeout(connect_timeout)
+                .build()
+                .map_err(|_| AuthError::Internal)?,
+            (None, None) => reqwest::Client::new(),
+        };
+
+        let mut api_urls = vec![api_url.clone()];
+        api_urls.extend(self.fallback_api_urls);
 
         Ok(AuthClient {
-            http_client: reqwest::Client::new(),
-            supabase_api_url: api_url.clone(),
+            http_client,
+            api_urls: Arc::new(ApiUrls::new(
+                api_urls,
+                self.auth_path.as_deref().unwrap_or(DEFAULT_AUTH_PATH),
+            )),
             supabase_anon_key: anon_key.clone(),
             supabase_service_role_key: self.service_role_key,
             postgrest_client: Postgrest::new(format!("{}/rest/v1/", api_url))
                 .schema("auth")
                 .insert_header("apikey", &anon_key),
+            context: Arc::default(),
+            password_policy: self.password_policy,
+            email_normalization: self.email_normalization,
+            jwt_verifier: self.jwt_verifier.map(Arc::new),
+            auth_event_hook: self.auth_event_hook,
+            request_timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            default_redirect_to: self.redirect_to,
+            redaction: self.redaction,
+            refresh_flights: Arc::default(),
+            client_info: self
+                .client_info
+                .unwrap_or_else(|| DEFAULT_CLIENT_INFO.to_string()),
         })
     }
 }
 
 /// Error response from the GoTrue/Supabase Auth API
-#[derive(Debug, Error, Deserialize, Serialize)]
+#[derive(Debug, Clone, Error, Deserialize, Serialize)]
 pub struct GoTrueErrorResponse {
     /// Error code number from the API
-    pub code: Option<u8>,
+    pub code: Option<u16>,
     /// Primary error message
     pub error: Option<String>,
     /// Detailed error description
@@ -227,10 +653,30 @@ impl Display for GoTrueErrorResponse {
 }
 
 /// Identifier type for authentication operations
-#[derive(Debug)]
+///
+/// `Display` masks most characters of the identifier (`t***@example.com`,
+/// `+1415•••0100`), so it is safe to log. Serialized as `{"email": ...}` or
+/// `{"phone": ...}`.
+///
+/// The variants are sent as given; construct identifiers from user input with
+/// [`IdType::email`], [`IdType::phone`] or `IdType::try_from` to reject malformed ones
+/// before they reach the server.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum IdType {
     /// Email-based authentication
     Email(String),
     /// Phone number-based authentication
+    #[serde(rename = "phone")]
     PhoneNumber(String),
 }
+
+impl Display for IdType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdType::Email(value) | IdType::PhoneNumber(value) => {
+                f.write_str(&util::mask_identifier(value))
+            }
+        }
+    }
+}
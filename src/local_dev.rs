@@ -0,0 +1,150 @@
+//! Discovery of the JWT secret of a local `supabase start` stack
+//!
+//! Enabled with the `local-dev` feature. Never use these helpers to configure production
+//! clients: they fall back to the well-known default secret of the Supabase CLI.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::{error, info, instrument};
+
+use crate::error::AuthError;
+use crate::jwt::JwtVerifier;
+
+/// JWT secret used by the Supabase CLI when `auth.jwt_secret` is not configured
+pub const DEFAULT_LOCAL_JWT_SECRET: &str =
+    "super-secret-jwt-token-with-at-least-32-characters-long";
+
+/// Subset of `supabase/config.toml` holding the JWT secret
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CliConfig {
+    auth: CliAuthConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CliAuthConfig {
+    jwt_secret: Option<String>,
+}
+
+impl JwtVerifier {
+    /// Creates a verifier for the local stack of the Supabase CLI project containing the
+    /// current directory
+    ///
+    /// Searches the current directory and its parents for `supabase/config.toml`, see
+    /// [`JwtVerifier::from_local_project`]. If no project is found the CLI's default secret
+    /// is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the config cannot be read or parsed, or
+    /// references an environment variable that is not set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use supabase_auth_redux::jwt::JwtVerifier;
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// # fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::builder()
+    ///     .api_url("http://127.0.0.1:54321")
+    ///     .anon_key("your-local-anon-key")
+    ///     .jwt_verifier(JwtVerifier::discover_local()?)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument]
+    pub fn discover_local() -> Result<Self, AuthError> {
+        let current_dir = std::env::current_dir().map_err(|e| {
+            error!("{}", e);
+            AuthError::Internal
+        })?;
+        match find_project_dir(&current_dir) {
+            Some(project_dir) => Self::from_local_project(project_dir),
+            None => {
+                info!("no supabase project found, using the default local jwt secret");
+                Ok(Self::hs256(DEFAULT_LOCAL_JWT_SECRET))
+            }
+        }
+    }
+
+    /// Creates a verifier from the `supabase/config.toml` of the CLI project in `project_dir`
+    ///
+    /// Reads `auth.jwt_secret`, resolving `env(NAME)` references, and falls back to the CLI's
+    /// default secret when it is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the config cannot be read or parsed, or
+    /// references an environment variable that is not set.
+    #[instrument(skip_all)]
+    pub fn from_local_project(project_dir: impl AsRef<Path>) -> Result<Self, AuthError> {
+        let config_path = project_dir.as_ref().join("supabase").join("config.toml");
+        let config_text = std::fs::read_to_string(&config_path).map_err(|e| {
+            error!("reading {}: {}", config_path.display(), e);
+            AuthError::InvalidParameters(None)
+        })?;
+        let config: CliConfig = toml::from_str(&config_text).map_err(|e| {
+            error!("parsing {}: {}", config_path.display(), e);
+            AuthError::InvalidParameters(None)
+        })?;
+
+        let secret = match config.auth.jwt_secret {
+            Some(secret) => resolve_env(&secret)?,
+            None => DEFAULT_LOCAL_JWT_SECRET.to_string(),
+        };
+        info!(config = %config_path.display(), "discovered local jwt secret");
+        Ok(Self::hs256(&secret))
+    }
+
+    /// Creates a verifier from the output of `supabase status -o env`
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the output has no `JWT_SECRET` line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::jwt::JwtVerifier;
+    ///
+    /// let status = "API_URL=\"http://127.0.0.1:54321\"\nJWT_SECRET=\"super-secret-jwt-token-with-at-least-32-characters-long\"\n";
+    /// let verifier = JwtVerifier::from_status_env(status)?;
+    /// # Ok::<(), supabase_auth_redux::AuthError>(())
+    /// ```
+    pub fn from_status_env(status_output: &str) -> Result<Self, AuthError> {
+        status_output
+            .lines()
+            .filter_map(|line| line.trim().split_once('='))
+            .find(|(key, _)| *key == "JWT_SECRET")
+            .map(|(_, value)| Self::hs256(value.trim_matches('"')))
+            .ok_or_else(|| {
+                error!("no JWT_SECRET in status output");
+                AuthError::InvalidParameters(None)
+            })
+    }
+}
+
+/// Returns the closest ancestor of `dir` (including itself) holding `supabase/config.toml`
+fn find_project_dir(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("supabase").join("config.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Resolves the `env(NAME)` syntax of the CLI config
+fn resolve_env(value: &str) -> Result<String, AuthError> {
+    let Some(name) = value
+        .strip_prefix("env(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return Ok(value.to_string());
+    };
+    std::env::var(name).map_err(|_| {
+        error!("environment variable {} is not set", name);
+        AuthError::InvalidParameters(None)
+    })
+}
@@ -1,6 +1,8 @@
-use tracing::{debug, error, instrument, trace_span, Instrument};
+use reqwest::Method;
+use tracing::instrument;
 
-use crate::util::handle_response_code;
+use crate::models::session::SignOutScope;
+use crate::util::{Credentials, Endpoint};
 use crate::{AuthClient, AuthError};
 
 impl AuthClient {
@@ -32,34 +34,55 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip_all)]
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn logout(&self, token: &str) -> Result<(), AuthError> {
-        let resp = match self
-            .http_client
-            .post(format!("{}/auth/v1/{}", self.supabase_api_url, "logout"))
-            .bearer_auth(token)
-            .header("apiKey", &self.supabase_anon_key)
-            .send()
-            .instrument(trace_span!("gotrue logout user"))
+        self.observe("logout", None, self.logout_request(token, None))
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
+    }
+
+    /// Logs out the sessions of a user selected by `scope`
+    ///
+    /// [`SignOutScope::Global`] ends every session of the user, [`SignOutScope::Local`]
+    /// only the session of `token`, and [`SignOutScope::Others`] every session but the one
+    /// of `token`. Refresh tokens of ended sessions stop working immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::NotAuthorized` if the access token is invalid.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::session::SignOutScope;
+    ///
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// // Sign out every other device after a password change
+    /// client.logout_with_scope(access_token, SignOutScope::Others).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn logout_with_scope(
+        &self,
+        token: &str,
+        scope: SignOutScope,
+    ) -> Result<(), AuthError> {
+        self.observe("logout", None, self.logout_request(token, Some(scope)))
+            .await
+    }
 
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                log::error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result?;
+    async fn logout_request(
+        &self,
+        token: &str,
+        scope: Option<SignOutScope>,
+    ) -> Result<(), AuthError> {
+        let mut endpoint = Endpoint::new(Method::POST, "logout", Credentials::User(token));
+        if let Some(scope) = scope {
+            endpoint = endpoint.query("scope", scope.as_str());
+        }
+        self.execute_text(endpoint).await?;
 
         Ok(())
     }
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::mfa::{MfaChallengeResponse, MfaEnrollResponse, MfaFactorType};
+use crate::models::token::TokenResponse;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+#[derive(Debug, Serialize)]
+struct EnrollRequest<'a> {
+    factor_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    friendly_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phone: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct VerifyRequest<'a> {
+    challenge_id: Uuid,
+    code: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnenrollResponse {
+    id: Uuid,
+}
+
+impl AuthClient {
+    /// Enrolls a new MFA factor for the user owning an access token
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `factor_type` - The kind of factor to enroll
+    /// * `friendly_name` - A name to tell the user's factors apart
+    ///
+    /// # Returns
+    ///
+    /// Returns the unverified factor; TOTP factors carry the secret and QR code to show.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token is empty or the server rejects
+    /// the factor (e.g. a duplicate friendly name).
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::mfa::MfaFactorType;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let factor = client
+    ///     .mfa_enroll(access_token, MfaFactorType::Totp, Some("Authenticator"))
+    ///     .await?;
+    /// let challenge = client.mfa_challenge(access_token, factor.id).await?;
+    ///
+    /// // The user scans `factor.totp.qr_code` and enters the code shown by their app
+    /// let tokens = client
+    ///     .mfa_verify(access_token, factor.id, challenge.id, "123456")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn mfa_enroll(
+        &self,
+        access_token: &str,
+        factor_type: MfaFactorType,
+        friendly_name: Option<&str>,
+    ) -> Result<MfaEnrollResponse, AuthError> {
+        self.observe("mfa_enroll", None, async {
+            let body = EnrollRequest {
+                factor_type: factor_type.as_str(),
+                friendly_name,
+                phone: match &factor_type {
+                    MfaFactorType::Totp => None,
+                    MfaFactorType::Phone { phone } => Some(phone.as_str()),
+                },
+            };
+            let resp_text = self
+                .factors_request(access_token, "factors", &body)
+                .instrument(trace_span!("gotrue mfa enroll"))
+                .await?;
+
+            let factor = match serde_json::from_str::<MfaEnrollResponse>(&resp_text) {
+                Ok(factor) => factor,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(factor_id = factor.id.to_string(), "enrolled mfa factor");
+
+            Ok(factor)
+        })
+        .await
+    }
+
+    /// Creates a challenge for an MFA factor
+    ///
+    /// Phone factors are sent a code by text message; TOTP factors are verified with the
+    /// code of the user's authenticator app.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `factor_id` - The factor to challenge
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::NotFound` if the factor does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn mfa_challenge(
+        &self,
+        access_token: &str,
+        factor_id: Uuid,
+    ) -> Result<MfaChallengeResponse, AuthError> {
+        self.observe("mfa_challenge", Some(factor_id.to_string()), async {
+            let resp_text = self
+                .factors_request(
+                    access_token,
+                    &format!("factors/{}/challenge", factor_id),
+                    &serde_json::json!({}),
+                )
+                .instrument(trace_span!("gotrue mfa challenge"))
+                .await?;
+
+            match serde_json::from_str::<MfaChallengeResponse>(&resp_text) {
+                Ok(challenge) => Ok(challenge),
+                Err(e) => {
+                    error!("{}", e);
+                    Err(AuthError::Internal)
+                }
+            }
+        })
+        .await
+    }
+
+    /// Verifies an MFA challenge with the code entered by the user
+    ///
+    /// A successful verification marks the factor as verified and returns a session with
+    /// assurance level `aal2`, replacing the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `factor_id` - The challenged factor
+    /// * `challenge_id` - The challenge returned by [`AuthClient::mfa_challenge`]
+    /// * `code` - The code from the authenticator app or text message
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the code is empty or wrong.
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::NotFound` if the factor or challenge does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn mfa_verify(
+        &self,
+        access_token: &str,
+        factor_id: Uuid,
+        challenge_id: Uuid,
+        code: &str,
+    ) -> Result<TokenResponse, AuthError> {
+        self.observe("mfa_verify", Some(factor_id.to_string()), async {
+            if code.is_empty() {
+                error!("empty code");
+                return Err(AuthError::InvalidParameters(None));
+            }
+            let resp_text = self
+                .factors_request(
+                    access_token,
+                    &format!("factors/{}/verify", factor_id),
+                    &VerifyRequest { challenge_id, code },
+                )
+                .instrument(trace_span!("gotrue mfa verify"))
+                .await?;
+
+            let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+                Ok(token_response) => token_response,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!("verified mfa factor");
+
+            Ok(token_response)
+        })
+        .await
+    }
+
+    /// Removes an MFA factor of the user owning an access token
+    ///
+    /// Removing a verified factor requires an `aal2` session.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `factor_id` - The factor to remove
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::NotAuthorized` if the token is invalid, expired or only `aal1`.
+    /// Returns `AuthError::NotFound` if the factor does not exist.
+    /// Returns `AuthError::Http` if the API request fails.
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn mfa_unenroll(&self, access_token: &str, factor_id: Uuid) -> Result<(), AuthError> {
+        self.observe("mfa_unenroll", Some(factor_id.to_string()), async {
+            if access_token.is_empty() {
+                error!("empty token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .delete(format!("{}/factors/{}", api_url, factor_id))
+                        .bearer_auth(access_token)
+                        .header("apiKey", &self.supabase_anon_key)
+                })
+                .instrument(trace_span!("gotrue mfa unenroll"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            if let Ok(unenrolled) = serde_json::from_str::<UnenrollResponse>(&resp_text) {
+                info!(
+                    factor_id = unenrolled.id.to_string(),
+                    "unenrolled mfa factor"
+                );
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Posts `body` to a `/factors` endpoint on behalf of the user owning `access_token`
+    async fn factors_request(
+        &self,
+        access_token: &str,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<String, AuthError> {
+        if access_token.is_empty() {
+            error!("empty token");
+            return Err(AuthError::InvalidParameters(None));
+        }
+
+        let resp = match self
+            .send_with_failover(|api_url| {
+                self.http_client
+                    .post(format!("{}/{}", api_url, path))
+                    .bearer_auth(access_token)
+                    .header("apiKey", &self.supabase_anon_key)
+                    .json(body)
+            })
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+
+        let resp_code_result = handle_response(&resp).await;
+        let resp_text = match resp.text().await {
+            Ok(resp_text) => resp_text,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        debug!("resp_text: {}", self.redaction.response(&resp_text));
+        resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+        Ok(resp_text)
+    }
+}
@@ -0,0 +1,363 @@
+//! Helpers for migrating users exported from other authentication providers
+//!
+//! The conversion functions turn Firebase (`firebase auth:export`) and Auth0 user export
+//! records into [`AdminCreateUserParams`], carrying over password hashes where GoTrue can
+//! verify them:
+//!
+//! * Firebase scrypt hashes are re-encoded in GoTrue's `$fbscrypt$` format, which requires
+//!   the project's hash parameters ([`FirebaseHashConfig`]).
+//! * Auth0 bcrypt hashes (`$2a$`, `$2b$`, `$2y$`) are passed through unchanged.
+//!
+//! Users whose password cannot be migrated are still imported, but have to go through
+//! password recovery before they can sign in with a password again.
+//!
+//! [`AuthClient::import_users`] then drives the bulk import and reports the outcome per record.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::admin::{AdminCreateUserParams, PasswordHashFormat, PERMANENT_BAN_DURATION};
+use crate::AuthClient;
+
+/// A user export produced by `firebase auth:export --format=json`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FirebaseExport {
+    /// The exported users
+    pub users: Vec<FirebaseUser>,
+}
+
+/// A single user record of a Firebase export
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FirebaseUser {
+    /// Firebase user ID
+    pub local_id: String,
+    /// Email address of the user
+    pub email: Option<String>,
+    /// Whether the email address has been verified
+    pub email_verified: bool,
+    /// Base64 encoded scrypt password hash
+    pub password_hash: Option<String>,
+    /// Base64 encoded per-user salt
+    pub salt: Option<String>,
+    /// Display name of the user
+    pub display_name: Option<String>,
+    /// Profile picture URL of the user
+    pub photo_url: Option<String>,
+    /// Phone number of the user
+    pub phone_number: Option<String>,
+    /// Whether the account is disabled
+    pub disabled: bool,
+}
+
+/// Password hash parameters of a Firebase project
+///
+/// These are shown in the Firebase console under Authentication > Users > Password hash
+/// parameters and use the same field names.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FirebaseHashConfig {
+    /// Base64 encoded signer key
+    pub base64_signer_key: String,
+    /// Base64 encoded salt separator
+    pub base64_salt_separator: String,
+    /// Scrypt block size (`r`)
+    pub rounds: u32,
+    /// Scrypt CPU/memory cost exponent (`N = 2^mem_cost`)
+    pub mem_cost: u32,
+}
+
+/// A single user export record of an Auth0 tenant
+///
+/// Password hashes are only part of exports requested from Auth0 support, where they are
+/// provided as bcrypt hashes in the `passwordHash` field.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Auth0User {
+    /// Auth0 user ID (e.g. `auth0|5f7c8ec7c33c6c004bbafe82`)
+    pub user_id: Option<String>,
+    /// Email address of the user
+    pub email: Option<String>,
+    /// Whether the email address has been verified
+    pub email_verified: bool,
+    /// Phone number of the user
+    pub phone_number: Option<String>,
+    /// Whether the phone number has been verified
+    pub phone_verified: bool,
+    /// Bcrypt password hash
+    #[serde(rename = "passwordHash", alias = "password_hash")]
+    pub password_hash: Option<String>,
+    /// Custom user metadata
+    pub user_metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Custom app metadata
+    pub app_metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// A user ready to be imported with [`AuthClient::import_users`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationRecord {
+    /// ID of the user in the source system, used for reporting
+    pub source_id: String,
+    /// Parameters used to create the user
+    pub params: AdminCreateUserParams,
+    /// Whether the password hash could be carried over
+    pub password_migrated: bool,
+}
+
+/// Progress information passed to the callback of [`AuthClient::import_users`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// Number of records processed so far
+    pub processed: usize,
+    /// Total number of records, if known up front
+    pub total: Option<usize>,
+    /// Number of records imported successfully so far
+    pub succeeded: usize,
+    /// Number of records that failed to import so far
+    pub failed: usize,
+}
+
+/// A user that was imported successfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedUser {
+    /// ID of the user in the source system
+    pub source_id: String,
+    /// ID of the newly created Supabase user
+    pub user_id: Uuid,
+    /// Whether the password hash was carried over
+    pub password_migrated: bool,
+}
+
+/// A user that could not be imported
+#[derive(Debug, Clone)]
+pub struct FailedImport {
+    /// ID of the user in the source system
+    pub source_id: String,
+    /// The error returned while creating the user
+    pub error: AuthError,
+}
+
+/// Outcome of a bulk import
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    /// Users that were created
+    pub imported: Vec<ImportedUser>,
+    /// Users that could not be created
+    pub failed: Vec<FailedImport>,
+}
+
+impl MigrationReport {
+    /// Source IDs of imported users that need a password reset before signing in
+    pub fn passwords_not_migrated(&self) -> impl Iterator<Item = &str> {
+        self.imported
+            .iter()
+            .filter(|user| !user.password_migrated)
+            .map(|user| user.source_id.as_str())
+    }
+}
+
+impl FirebaseUser {
+    /// Converts the record into a [`MigrationRecord`]
+    ///
+    /// The password hash is only carried over when `hash_config` is provided and the record
+    /// contains both a hash and a salt. Disabled accounts are imported as permanently
+    /// banned users.
+    pub fn into_migration_record(
+        self,
+        hash_config: Option<&FirebaseHashConfig>,
+    ) -> MigrationRecord {
+        let password_hash = match (hash_config, &self.password_hash, &self.salt) {
+            (Some(config), Some(hash), Some(salt)) if !hash.is_empty() => {
+                Some(firebase_scrypt_hash(config, salt, hash))
+            }
+            _ => None,
+        };
+
+        let mut user_metadata = HashMap::new();
+        if let Some(display_name) = self.display_name {
+            user_metadata.insert("full_name".to_string(), display_name.into());
+        }
+        if let Some(photo_url) = self.photo_url {
+            user_metadata.insert("avatar_url".to_string(), photo_url.into());
+        }
+
+        MigrationRecord {
+            password_migrated: password_hash.is_some(),
+            params: AdminCreateUserParams {
+                email: self.email,
+                phone: self.phone_number,
+                password_hash,
+                email_confirm: self.email_verified,
+                user_metadata: (!user_metadata.is_empty()).then_some(user_metadata),
+                app_metadata: Some(migration_app_metadata("firebase", &self.local_id)),
+                ban_duration: self.disabled.then(|| PERMANENT_BAN_DURATION.to_string()),
+                ..Default::default()
+            },
+            source_id: self.local_id,
+        }
+    }
+}
+
+impl Auth0User {
+    /// Converts the record into a [`MigrationRecord`]
+    ///
+    /// Only bcrypt password hashes are carried over. The source ID falls back to the email
+    /// address or phone number for records without a `user_id`.
+    pub fn into_migration_record(self) -> MigrationRecord {
+        let password_hash = self.password_hash.filter(|hash| is_bcrypt_hash(hash));
+        let source_id = self
+            .user_id
+            .clone()
+            .or_else(|| self.email.clone())
+            .or_else(|| self.phone_number.clone())
+            .unwrap_or_default();
+
+        let mut app_metadata = self.app_metadata.unwrap_or_default();
+        app_metadata.extend(migration_app_metadata("auth0", &source_id));
+
+        MigrationRecord {
+            password_migrated: password_hash.is_some(),
+            params: AdminCreateUserParams {
+                email: self.email,
+                phone: self.phone_number,
+                password_hash,
+                email_confirm: self.email_verified,
+                phone_confirm: self.phone_verified,
+                user_metadata: self.user_metadata,
+                app_metadata: Some(app_metadata),
+                ..Default::default()
+            },
+            source_id,
+        }
+    }
+}
+
+/// Converts a whole Firebase export into migration records
+pub fn firebase_records(
+    export: FirebaseExport,
+    hash_config: Option<&FirebaseHashConfig>,
+) -> Vec<MigrationRecord> {
+    export
+        .users
+        .into_iter()
+        .map(|user| user.into_migration_record(hash_config))
+        .collect()
+}
+
+/// Converts Auth0 export records into migration records
+pub fn auth0_records(users: impl IntoIterator<Item = Auth0User>) -> Vec<MigrationRecord> {
+    users
+        .into_iter()
+        .map(Auth0User::into_migration_record)
+        .collect()
+}
+
+/// Encodes a Firebase scrypt hash in the `$fbscrypt$` format understood by GoTrue
+fn firebase_scrypt_hash(config: &FirebaseHashConfig, salt: &str, hash: &str) -> String {
+    format!(
+        "$fbscrypt$v=1,n={},r={},p=1,ss={},sk={}${}${}",
+        config.mem_cost,
+        config.rounds,
+        config.base64_salt_separator,
+        config.base64_signer_key,
+        salt,
+        hash
+    )
+}
+
+fn is_bcrypt_hash(hash: &str) -> bool {
+    PasswordHashFormat::detect(hash) == Some(PasswordHashFormat::Bcrypt)
+}
+
+fn migration_app_metadata(source: &str, source_id: &str) -> HashMap<String, serde_json::Value> {
+    HashMap::from([
+        ("migrated_from".to_string(), source.into()),
+        ("legacy_id".to_string(), source_id.into()),
+    ])
+}
+
+impl AuthClient {
+    /// Imports users into Supabase Auth through the admin API
+    ///
+    /// Records are created one after another with [`AuthClient::admin_create_user`]. A failing
+    /// record does not abort the import; it is reported in [`MigrationReport::failed`] instead.
+    /// `on_progress` is called after every record.
+    ///
+    /// This operation requires a service role key to be configured on the AuthClient.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::migrate::{firebase_records, FirebaseExport, FirebaseHashConfig};
+    /// # async fn example(export_json: &str, hash_config: FirebaseHashConfig) -> Result<(), Box<dyn std::error::Error>> {
+    /// let admin_client = AuthClient::builder()
+    ///     .api_url("https://your-project.supabase.co")
+    ///     .anon_key("your-anon-key")
+    ///     .service_role_key("your-service-role-key")
+    ///     .build()?;
+    ///
+    /// let export: FirebaseExport = serde_json::from_str(export_json)?;
+    /// let records = firebase_records(export, Some(&hash_config));
+    ///
+    /// let report = admin_client
+    ///     .import_users(records, |progress| {
+    ///         println!("{}/{:?}", progress.processed, progress.total);
+    ///     })
+    ///     .await;
+    ///
+    /// println!("imported {}, failed {}", report.imported.len(), report.failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context))]
+    pub async fn import_users<I, F>(&self, records: I, mut on_progress: F) -> MigrationReport
+    where
+        I: IntoIterator<Item = MigrationRecord>,
+        F: FnMut(&MigrationProgress),
+    {
+        let records = records.into_iter();
+        let mut progress = MigrationProgress {
+            total: match records.size_hint() {
+                (lower, Some(upper)) if lower == upper => Some(lower),
+                _ => None,
+            },
+            ..Default::default()
+        };
+        let mut report = MigrationReport::default();
+
+        for record in records {
+            match self.admin_create_user(record.params).await {
+                Ok(user) => {
+                    progress.succeeded += 1;
+                    report.imported.push(ImportedUser {
+                        source_id: record.source_id,
+                        user_id: user.id,
+                        password_migrated: record.password_migrated,
+                    });
+                }
+                Err(error) => {
+                    warn!(source_id = record.source_id, %error, "failed to import user");
+                    progress.failed += 1;
+                    report.failed.push(FailedImport {
+                        source_id: record.source_id,
+                        error,
+                    });
+                }
+            }
+            progress.processed += 1;
+            on_progress(&progress);
+        }
+
+        info!(
+            imported = report.imported.len(),
+            failed = report.failed.len(),
+            "user import finished"
+        );
+        report
+    }
+}
@@ -0,0 +1,569 @@
+//! In-memory stand-in for the auth server
+//!
+//! Enabled with the `mock` feature. [`MockAuthServer`] serves the core GoTrue endpoints
+//! (signup, password and refresh token grants, user lookup and update, logout, admin user
+//! deletion and health) from memory on a random local port, so code using a real
+//! [`AuthClient`] can be tested without `supabase start`. Access tokens are HS256 JWTs
+//! signed with [`MockAuthServer::JWT_SECRET`], so local verification works as well.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use supabase_auth_redux::mock::MockAuthServer;
+//! use supabase_auth_redux::IdType;
+//!
+//! # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+//! let server = MockAuthServer::start().await?;
+//! let client = server.client();
+//!
+//! client
+//!     .signup(IdType::Email("user@example.com".to_string()), "password".to_string(), None)
+//!     .await?;
+//! let tokens = client
+//!     .signin_with_password(IdType::Email("user@example.com".to_string()), "password".to_string())
+//!     .await?;
+//! let user = client.get_user_by_token(&tokens.access_token).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use ::axum::extract::{Path, Query, State};
+use ::axum::http::{header, HeaderMap, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+use ::axum::routing::{delete, get, post};
+use ::axum::{Json, Router};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+use crate::jwt::JwtVerifier;
+use crate::models::token::TokenResponse;
+use crate::{AuthClient, AuthError, IdType, User};
+
+/// Validity of issued access tokens, in seconds
+const ACCESS_TOKEN_TTL: u64 = 3600;
+
+/// Auth server keeping users and sessions in memory
+///
+/// The server runs on the current Tokio runtime until it is dropped. Users sign up
+/// confirmed, as with email confirmation disabled.
+#[derive(Debug)]
+pub struct MockAuthServer {
+    /// Base URL of the server (e.g. `http://127.0.0.1:54321`)
+    url: String,
+    /// Users and sessions
+    state: Arc<Mutex<MockState>>,
+    /// Task serving requests
+    task: JoinHandle<()>,
+}
+
+impl Drop for MockAuthServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Users with their passwords
+    users: HashMap<Uuid, MockUser>,
+    /// User of each active session
+    sessions: HashMap<Uuid, Uuid>,
+    /// Session of each refresh token that was not rotated out yet
+    refresh_tokens: HashMap<String, Uuid>,
+}
+
+#[derive(Debug)]
+struct MockUser {
+    user: User,
+    password: String,
+}
+
+impl MockAuthServer {
+    /// Anonymous key accepted by the server
+    pub const ANON_KEY: &'static str = "mock-anon-key";
+    /// Service role key required by the admin endpoints
+    pub const SERVICE_ROLE_KEY: &'static str = "mock-service-role-key";
+    /// Secret access tokens are signed with
+    pub const JWT_SECRET: &'static str = "mock-jwt-secret";
+
+    /// Starts a server on a random local port
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if no port can be bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub async fn start() -> Result<Self, AuthError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(|e| {
+            error!("{}", e);
+            AuthError::Internal
+        })?;
+        let url = match listener.local_addr() {
+            Ok(addr) => format!("http://{}", addr),
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let router = Router::new()
+            .route("/auth/v1/health", get(health))
+            .route("/auth/v1/signup", post(signup))
+            .route("/auth/v1/token", post(token))
+            .route("/auth/v1/user", get(get_user).put(update_user))
+            .route("/auth/v1/logout", post(logout))
+            .route("/auth/v1/admin/users/{user_id}", delete(delete_user))
+            .fallback(|| async { api_error(StatusCode::NOT_FOUND, "not_found", "Not found") })
+            .with_state(state.clone());
+        let task = tokio::spawn(async move {
+            if let Err(e) = ::axum::serve(listener, router).await {
+                error!("mock auth server failed: {}", e);
+            }
+        });
+        debug!(url, "mock auth server started");
+
+        Ok(Self { url, state, task })
+    }
+
+    /// Returns the base URL of the server
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns a client for the server, configured with its service role key and a
+    /// verifier for its access tokens
+    pub fn client(&self) -> AuthClient {
+        AuthClient::builder()
+            .api_url(&self.url)
+            .anon_key(Self::ANON_KEY)
+            .service_role_key(Self::SERVICE_ROLE_KEY)
+            .jwt_verifier(JwtVerifier::hs256(Self::JWT_SECRET))
+            .build()
+            .expect("mock client configuration is valid")
+    }
+
+    /// Adds a confirmed user signing in with `id` and `password` and returns it
+    pub fn create_user(&self, id: IdType, password: &str) -> User {
+        let mut state = lock(&self.state);
+        state.insert_user(id, password.to_string(), None)
+    }
+
+    /// Returns all users, in no particular order
+    pub fn users(&self) -> Vec<User> {
+        lock(&self.state)
+            .users
+            .values()
+            .map(|mock_user| mock_user.user.clone())
+            .collect()
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> MutexGuard<'_, MockState> {
+    // Keep serving later requests after a handler panicked
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl MockState {
+    fn find_user(&self, id: &IdType) -> Option<&MockUser> {
+        self.users.values().find(|mock_user| match id {
+            IdType::Email(email) => mock_user.user.email.as_deref() == Some(email.as_str()),
+            IdType::PhoneNumber(phone) => mock_user.user.phone.as_deref() == Some(phone.as_str()),
+        })
+    }
+
+    fn insert_user(
+        &mut self,
+        id: IdType,
+        password: String,
+        user_metadata: Option<HashMap<String, Value>>,
+    ) -> User {
+        let now = OffsetDateTime::now_utc();
+        let provider = match &id {
+            IdType::Email(_) => "email",
+            IdType::PhoneNumber(_) => "phone",
+        };
+        let mut user = User {
+            id: Uuid::new_v4(),
+            aud: "authenticated".to_string(),
+            role: "authenticated".to_string(),
+            confirmed_at: Some(now),
+            user_metadata: Some(user_metadata.unwrap_or_default()),
+            app_metadata: Some(HashMap::from([
+                ("provider".to_string(), json!(provider)),
+                ("providers".to_string(), json!([provider])),
+            ])),
+            created_at: Some(now),
+            updated_at: Some(now),
+            ..Default::default()
+        };
+        match id {
+            IdType::Email(email) => {
+                user.email = Some(email);
+                user.email_confirmed_at = Some(now);
+            }
+            IdType::PhoneNumber(phone) => {
+                user.phone = Some(phone);
+                user.phone_confirmed_at = Some(now);
+            }
+        }
+        self.users.insert(
+            user.id,
+            MockUser {
+                user: user.clone(),
+                password,
+            },
+        );
+        user
+    }
+
+    /// Starts a session for `user_id` and returns its tokens
+    fn issue_session(&mut self, user_id: Uuid) -> Result<TokenResponse, MockError> {
+        let session_id = Uuid::new_v4();
+        self.sessions.insert(session_id, user_id);
+        self.issue_tokens(user_id, session_id)
+    }
+
+    fn issue_tokens(
+        &mut self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<TokenResponse, MockError> {
+        let Some(mock_user) = self.users.get_mut(&user_id) else {
+            return Err(MockError {
+                status: StatusCode::NOT_FOUND,
+                error_code: "user_not_found",
+                msg: "User not found",
+            });
+        };
+        mock_user.user.last_sign_in_at = Some(OffsetDateTime::now_utc());
+        let user = mock_user.user.clone();
+
+        let now = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        let claims = json!({
+            "sub": user.id,
+            "aud": "authenticated",
+            "role": "authenticated",
+            "iat": now,
+            "exp": now + ACCESS_TOKEN_TTL,
+            "email": user.email,
+            "phone": user.phone,
+            "session_id": session_id,
+            "aal": "aal1",
+            "app_metadata": user.app_metadata,
+            "user_metadata": user.user_metadata,
+        });
+        let access_token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(MockAuthServer::JWT_SECRET.as_bytes()),
+        )
+        .map_err(|e| {
+            error!("{}", e);
+            MockError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                error_code: "unexpected_failure",
+                msg: "Signing failed",
+            }
+        })?;
+        let refresh_token = Uuid::new_v4().simple().to_string();
+        self.refresh_tokens
+            .insert(refresh_token.clone(), session_id);
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL,
+            expires_at: Some(now + ACCESS_TOKEN_TTL),
+            refresh_token,
+            user: Some(user),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the session and user of the request's bearer token
+    fn authenticate(&self, headers: &HeaderMap) -> Result<(Uuid, Uuid), MockError> {
+        let unauthorized = || MockError {
+            status: StatusCode::UNAUTHORIZED,
+            error_code: "bad_jwt",
+            msg: "invalid JWT: unable to parse or verify signature",
+        };
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(unauthorized)?;
+        let claims = JwtVerifier::hs256(MockAuthServer::JWT_SECRET)
+            .verify(token)
+            .map_err(|_| unauthorized())?;
+        let session_id = claims.session_id.ok_or_else(unauthorized)?;
+        match self.sessions.get(&session_id) {
+            Some(user_id) if *user_id == claims.sub => Ok((session_id, claims.sub)),
+            _ => Err(MockError {
+                status: StatusCode::FORBIDDEN,
+                error_code: "session_not_found",
+                msg: "Session from session_id claim in JWT does not exist",
+            }),
+        }
+    }
+
+    /// Ends a session, invalidating its access and refresh tokens
+    fn end_session(&mut self, session_id: Uuid) {
+        self.sessions.remove(&session_id);
+        self.refresh_tokens
+            .retain(|_, session| *session != session_id);
+    }
+}
+
+/// A GoTrue error of a [`MockState`] operation, turned into a response by the handlers
+#[derive(Debug)]
+struct MockError {
+    status: StatusCode,
+    error_code: &'static str,
+    msg: &'static str,
+}
+
+impl IntoResponse for MockError {
+    fn into_response(self) -> Response {
+        api_error(self.status, self.error_code, self.msg)
+    }
+}
+
+/// Responds with a GoTrue error body
+fn api_error(status: StatusCode, error_code: &str, msg: &str) -> Response {
+    let body = json!({ "code": status.as_u16(), "error_code": error_code, "msg": msg });
+    (status, Json(body)).into_response()
+}
+
+/// Returns the email address or phone number of a request body
+fn id_of(body: &Value) -> Option<IdType> {
+    let field = |name: &str| {
+        body.get(name)
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    field("email").map(IdType::Email).or_else(|| {
+        field("phone")
+            .or_else(|| field("phone_number"))
+            .map(IdType::PhoneNumber)
+    })
+}
+
+async fn health() -> Response {
+    Json(json!({
+        "version": "mock",
+        "name": "GoTrue",
+        "description": "In-memory mock of GoTrue",
+    }))
+    .into_response()
+}
+
+async fn signup(State(state): State<Arc<Mutex<MockState>>>, Json(body): Json<Value>) -> Response {
+    let mut state = lock(&state);
+    let Some(id) = id_of(&body) else {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            "validation_failed",
+            "Signup requires a valid password",
+        );
+    };
+    let password = body["password"].as_str().unwrap_or_default().to_string();
+    if password.is_empty() {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            "validation_failed",
+            "Signup requires a valid password",
+        );
+    }
+    if state.find_user(&id).is_some() {
+        return api_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "user_already_exists",
+            "User already registered",
+        );
+    }
+    let user_metadata = serde_json::from_value(body["data"].clone()).unwrap_or_default();
+    let user = state.insert_user(id, password, user_metadata);
+    match state.issue_session(user.id) {
+        Ok(tokens) => Json(tokens).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GrantType {
+    grant_type: String,
+}
+
+async fn token(
+    State(state): State<Arc<Mutex<MockState>>>,
+    Query(query): Query<GrantType>,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut state = lock(&state);
+    let tokens = match query.grant_type.as_str() {
+        "password" => {
+            let password = body["password"].as_str().unwrap_or_default();
+            let user_id = id_of(&body)
+                .and_then(|id| state.find_user(&id))
+                .filter(|mock_user| mock_user.password == password)
+                .map(|mock_user| mock_user.user.id);
+            let Some(user_id) = user_id else {
+                return api_error(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_credentials",
+                    "Invalid login credentials",
+                );
+            };
+            state.issue_session(user_id)
+        }
+        "refresh_token" => {
+            let refresh_token = body["refresh_token"].as_str().unwrap_or_default();
+            // Refresh tokens are rotated, so each can be used once
+            let Some(session_id) = state.refresh_tokens.remove(refresh_token) else {
+                return api_error(
+                    StatusCode::BAD_REQUEST,
+                    "refresh_token_not_found",
+                    "Invalid Refresh Token: Refresh Token Not Found",
+                );
+            };
+            let Some(user_id) = state.sessions.get(&session_id).copied() else {
+                return api_error(
+                    StatusCode::BAD_REQUEST,
+                    "session_not_found",
+                    "Invalid Refresh Token: Session Expired",
+                );
+            };
+            state.issue_tokens(user_id, session_id)
+        }
+        _ => {
+            return api_error(
+                StatusCode::BAD_REQUEST,
+                "validation_failed",
+                "Unsupported grant type",
+            )
+        }
+    };
+    match tokens {
+        Ok(tokens) => Json(tokens).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_user(State(state): State<Arc<Mutex<MockState>>>, headers: HeaderMap) -> Response {
+    let state = lock(&state);
+    let user_id = match state.authenticate(&headers) {
+        Ok((_, user_id)) => user_id,
+        Err(e) => return e.into_response(),
+    };
+    match state.users.get(&user_id) {
+        Some(mock_user) => Json(&mock_user.user).into_response(),
+        None => api_error(StatusCode::NOT_FOUND, "user_not_found", "User not found"),
+    }
+}
+
+async fn update_user(
+    State(state): State<Arc<Mutex<MockState>>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut state = lock(&state);
+    let user_id = match state.authenticate(&headers) {
+        Ok((_, user_id)) => user_id,
+        Err(e) => return e.into_response(),
+    };
+    if let Some(id) = id_of(&body) {
+        if state
+            .find_user(&id)
+            .is_some_and(|mock_user| mock_user.user.id != user_id)
+        {
+            let error_code = match id {
+                IdType::Email(_) => "email_exists",
+                IdType::PhoneNumber(_) => "phone_exists",
+            };
+            return api_error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                error_code,
+                "A user with this identifier has already been registered",
+            );
+        }
+    }
+    let Some(mock_user) = state.users.get_mut(&user_id) else {
+        return api_error(StatusCode::NOT_FOUND, "user_not_found", "User not found");
+    };
+    // Changes take effect immediately, as with confirmations disabled
+    match id_of(&body) {
+        Some(IdType::Email(email)) => mock_user.user.email = Some(email),
+        Some(IdType::PhoneNumber(phone)) => mock_user.user.phone = Some(phone),
+        None => {}
+    }
+    if let Some(password) = body["password"].as_str() {
+        mock_user.password = password.to_string();
+    }
+    if let Some(data) = body["data"].as_object() {
+        let user_metadata = mock_user
+            .user
+            .user_metadata
+            .get_or_insert_with(HashMap::new);
+        for (key, value) in data {
+            user_metadata.insert(key.clone(), value.clone());
+        }
+    }
+    mock_user.user.updated_at = Some(OffsetDateTime::now_utc());
+    Json(&mock_user.user).into_response()
+}
+
+async fn logout(State(state): State<Arc<Mutex<MockState>>>, headers: HeaderMap) -> Response {
+    let mut state = lock(&state);
+    match state.authenticate(&headers) {
+        Ok((session_id, _)) => {
+            state.end_session(session_id);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn delete_user(
+    State(state): State<Arc<Mutex<MockState>>>,
+    Path(user_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    let service_role = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        == Some(MockAuthServer::SERVICE_ROLE_KEY);
+    if !service_role {
+        return api_error(StatusCode::FORBIDDEN, "not_admin", "User not allowed");
+    }
+
+    let mut state = lock(&state);
+    if state.users.remove(&user_id).is_none() {
+        return api_error(StatusCode::NOT_FOUND, "user_not_found", "User not found");
+    }
+    let sessions: Vec<Uuid> = state
+        .sessions
+        .iter()
+        .filter(|(_, owner)| **owner == user_id)
+        .map(|(session_id, _)| *session_id)
+        .collect();
+    for session_id in sessions {
+        state.end_session(session_id);
+    }
+    Json(json!({})).into_response()
+}
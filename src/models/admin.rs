@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::user::UserSchema;
+
+/// Ban duration of roughly 100 years, used to ban a user for good
+pub const PERMANENT_BAN_DURATION: &str = "876000h";
+
+/// Password hash formats GoTrue accepts for [`AdminCreateUserParams::password_hash`]
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::admin::PasswordHashFormat;
+///
+/// assert_eq!(
+///     PasswordHashFormat::detect("$2b$10$N9qo8uLOickgx2ZMRZoMyeIjZAgcfl7p92ldGxad68LJZdL17lhWy"),
+///     Some(PasswordHashFormat::Bcrypt)
+/// );
+/// assert_eq!(PasswordHashFormat::detect("5f4dcc3b5aa765d61d8327deb882cf99"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordHashFormat {
+    /// bcrypt, in modular crypt format: `$2a$`, `$2b$` or `$2y$` followed by the cost
+    Bcrypt,
+    /// Argon2i or Argon2id, as a PHC string: `$argon2id$v=19$m=...,t=...,p=...$<salt>$<hash>`
+    Argon2,
+    /// Firebase's modified scrypt:
+    /// `$fbscrypt$v=1,n=...,r=...,p=...,ss=...,sk=...$<salt>$<hash>`, as built by
+    /// [`FirebaseUser::into_migration_record`](crate::migrate::FirebaseUser::into_migration_record)
+    FirebaseScrypt,
+}
+
+impl PasswordHashFormat {
+    /// Returns the format of `hash`, or `None` if GoTrue does not accept it
+    ///
+    /// Only the prefix is checked; GoTrue validates the parameters and digest.
+    pub fn detect(hash: &str) -> Option<Self> {
+        if ["$2a$", "$2b$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            return Some(PasswordHashFormat::Bcrypt);
+        }
+        if ["$argon2i$", "$argon2id$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            return Some(PasswordHashFormat::Argon2);
+        }
+        if hash.starts_with("$fbscrypt$") {
+            return Some(PasswordHashFormat::FirebaseScrypt);
+        }
+        None
+    }
+}
+
+/// Parameters for creating a user through the admin API
+///
+/// Either `password` or `password_hash` may be provided. When both are omitted the user
+/// is created without a password and has to sign in through another flow (magic link,
+/// OTP, password recovery).
+///
+/// # Example
+///
+/// Importing a user from a legacy system without forcing a password reset:
+///
+/// ```rust
+/// use supabase_auth_redux::models::admin::AdminCreateUserParams;
+///
+/// let params = AdminCreateUserParams {
+///     email: Some("legacy@example.com".to_string()),
+///     password_hash: Some(
+///         "$2b$10$N9qo8uLOickgx2ZMRZoMyeIjZAgcfl7p92ldGxad68LJZdL17lhWy".to_string(),
+///     ),
+///     email_confirm: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct AdminCreateUserParams {
+    /// Explicit ID for the new user; generated by the server when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    /// Email address of the new user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Phone number of the new user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// Plain text password of the new user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Already hashed password of the new user, in one of the [`PasswordHashFormat`]s
+    ///
+    /// The user signs in with the original password, which GoTrue checks against the hash.
+    /// Other formats (e.g. unsalted MD5 or SHA digests) are rejected before the request is
+    /// sent; such users have to reset their password.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+    /// Marks the email address as confirmed without sending a confirmation email
+    pub email_confirm: bool,
+    /// Marks the phone number as confirmed without sending a confirmation SMS
+    pub phone_confirm: bool,
+    /// Custom user metadata that can be updated by the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Custom app metadata that can only be updated by service role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Bans the new user for a duration such as `"24h"` (Go duration syntax), e.g. to import
+    /// accounts suspended in the previous system with [`PERMANENT_BAN_DURATION`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban_duration: Option<String>,
+}
+
+/// Attributes of a user changed through the admin API
+///
+/// Attributes left as `None` are not changed. Metadata maps are merged into the stored
+/// metadata; set a key to `null` to remove it.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::admin::AdminUpdateUserParams;
+///
+/// let params = AdminUpdateUserParams {
+///     email_confirm: Some(true),
+///     ..Default::default()
+/// }
+/// .ban_for(time::Duration::days(7));
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct AdminUpdateUserParams {
+    /// New email address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// New phone number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// New plain text password
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// New Postgres role of the user's tokens, e.g. a custom `support_agent` role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Marks the email address as confirmed (or unconfirmed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_confirm: Option<bool>,
+    /// Marks the phone number as confirmed (or unconfirmed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_confirm: Option<bool>,
+    /// Custom user metadata entries to set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Custom app metadata entries to set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Bans the user for a duration such as `"24h"` (Go duration syntax) from now on;
+    /// `"none"` lifts a ban
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban_duration: Option<String>,
+}
+
+impl AdminUpdateUserParams {
+    /// Bans the user for `duration` from now on, setting `banned_until`
+    pub fn ban_for(mut self, duration: time::Duration) -> Self {
+        self.ban_duration = Some(format!("{}s", duration.whole_seconds().max(0)));
+        self
+    }
+
+    /// Lifts a ban of the user
+    pub fn unban(mut self) -> Self {
+        self.ban_duration = Some("none".to_string());
+        self
+    }
+}
+
+/// Attributes of an MFA factor changed through the admin API
+///
+/// Attributes left as `None` are not changed.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UpdateFactor {
+    /// New user-friendly name of the factor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friendly_name: Option<String>,
+    /// New phone number of a phone factor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+}
+
+/// Kind of link generated by the admin API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkType {
+    /// Signup confirmation link; creates the user if needed and requires a password
+    Signup,
+    /// Invitation link; creates the user if needed
+    Invite,
+    /// Magic link signing in an existing user
+    Magiclink,
+    /// Password recovery link
+    Recovery,
+    /// Email change confirmation link sent to the current address
+    EmailChangeCurrent,
+    /// Email change confirmation link sent to the new address
+    EmailChangeNew,
+}
+
+/// Parameters for generating a link through the admin API
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::admin::{GenerateLinkParams, LinkType};
+///
+/// let params = GenerateLinkParams::new(LinkType::Recovery, "user@example.com")
+///     .redirect_to("https://app.example.com/reset-password");
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct GenerateLinkParams {
+    /// Kind of link to generate
+    #[serde(rename = "type")]
+    pub link_type: LinkType,
+    /// Email address of the user
+    pub email: String,
+    /// New email address, required for email change links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_email: Option<String>,
+    /// Password of the new user, required for signup links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Metadata stored on the user if the link creates it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<HashMap<String, serde_json::Value>>,
+    /// URL the link redirects to after verification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_to: Option<String>,
+}
+
+impl GenerateLinkParams {
+    /// Creates parameters for a link of `link_type` for `email`
+    pub fn new(link_type: LinkType, email: &str) -> Self {
+        Self {
+            link_type,
+            email: email.to_string(),
+            new_email: None,
+            password: None,
+            data: None,
+            redirect_to: None,
+        }
+    }
+
+    /// Sets the new email address of an email change link
+    pub fn new_email(mut self, new_email: &str) -> Self {
+        self.new_email = Some(new_email.to_string());
+        self
+    }
+
+    /// Sets the password of a signup link
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    /// Sets the metadata stored on a created user
+    pub fn data(mut self, data: HashMap<String, serde_json::Value>) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the URL the link redirects to
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+}
+
+/// How [`AuthClient::admin_send_link`](crate::AuthClient::admin_send_link) delivers a link
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LinkDelivery {
+    /// GoTrue sends its email to the user
+    #[default]
+    Email,
+    /// No email is sent; the link and code are returned for custom delivery
+    Return,
+}
+
+/// Link generated by the admin API, to be delivered through your own email provider
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GenerateLinkResponse {
+    /// Link verifying the token and redirecting to `redirect_to`
+    pub action_link: String,
+    /// One-time code equivalent to the link, for code-based email templates
+    pub email_otp: String,
+    /// Hashed token, for links pointing at your own confirmation endpoint
+    pub hashed_token: String,
+    /// Verification type to pass along with `hashed_token`
+    pub verification_type: String,
+    /// URL the link redirects to
+    pub redirect_to: String,
+    /// The user the link was generated for
+    #[serde(flatten)]
+    pub user: UserSchema,
+}
+
+/// A session of a user, as returned by the admin API
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionSchema {
+    /// Unique identifier for the session
+    pub id: Uuid,
+    /// The user the session belongs to
+    pub user_id: Uuid,
+    /// Timestamp when the session was created
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// Timestamp when the session was last updated
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    /// Timestamp of the last token refresh
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub refreshed_at: Option<OffsetDateTime>,
+    /// Timestamp after which the session cannot be refreshed, if time-boxed
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub not_after: Option<OffsetDateTime>,
+    /// MFA factor used to reach `aal2`, if any
+    pub factor_id: Option<Uuid>,
+    /// Authenticator assurance level (`aal1` or `aal2`)
+    pub aal: Option<String>,
+    /// User agent of the client that created the session
+    pub user_agent: Option<String>,
+    /// IP address of the client that created the session
+    pub ip: Option<String>,
+    /// Tag assigned to the session, if any
+    pub tag: Option<String>,
+}
+
+/// A page of users returned by the admin user listing
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct UserList {
+    /// Users on the requested page
+    pub users: Vec<UserSchema>,
+    /// Total number of users, from the `X-Total-Count` header
+    pub total: Option<u64>,
+    /// Page following the requested one, `None` on the last page
+    pub next_page: Option<u32>,
+}
+
+/// Filter applied when listing users through the admin API
+///
+/// `search` is evaluated by the server. Date ranges and the confirmation status are
+/// evaluated client-side while paging through the users; listings restricted by
+/// `created_after` stop paging as soon as older users are reached. All bounds are
+/// inclusive, and users without a `last_sign_in_at` (or `deleted_at`) never match a sign-in
+/// (or deletion) range.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::admin::UserListFilter;
+/// use time::{Duration, OffsetDateTime};
+///
+/// // Signed up during the last week, never confirmed
+/// let filter = UserListFilter::new()
+///     .created_after(OffsetDateTime::now_utc() - Duration::weeks(1))
+///     .email_confirmed(false);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserListFilter {
+    /// Server-side search on the email address and name of the user
+    pub search: Option<String>,
+    /// Only users created at or after this instant
+    pub created_after: Option<OffsetDateTime>,
+    /// Only users created at or before this instant
+    pub created_before: Option<OffsetDateTime>,
+    /// Only users that last signed in at or after this instant
+    pub last_sign_in_after: Option<OffsetDateTime>,
+    /// Only users that last signed in at or before this instant
+    pub last_sign_in_before: Option<OffsetDateTime>,
+    /// Only users whose email confirmation status matches
+    pub email_confirmed: Option<bool>,
+    /// Only users whose soft deletion status matches
+    pub deleted: Option<bool>,
+    /// Only users soft deleted at or before this instant
+    pub deleted_before: Option<OffsetDateTime>,
+}
+
+impl UserListFilter {
+    /// Creates a filter matching every user
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the server-side search term
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Only matches users created at or after `instant`
+    pub fn created_after(mut self, instant: OffsetDateTime) -> Self {
+        self.created_after = Some(instant);
+        self
+    }
+
+    /// Only matches users created at or before `instant`
+    pub fn created_before(mut self, instant: OffsetDateTime) -> Self {
+        self.created_before = Some(instant);
+        self
+    }
+
+    /// Only matches users that last signed in at or after `instant`
+    pub fn last_sign_in_after(mut self, instant: OffsetDateTime) -> Self {
+        self.last_sign_in_after = Some(instant);
+        self
+    }
+
+    /// Only matches users that last signed in at or before `instant`
+    pub fn last_sign_in_before(mut self, instant: OffsetDateTime) -> Self {
+        self.last_sign_in_before = Some(instant);
+        self
+    }
+
+    /// Only matches users with the given email confirmation status
+    pub fn email_confirmed(mut self, confirmed: bool) -> Self {
+        self.email_confirmed = Some(confirmed);
+        self
+    }
+
+    /// Only matches users with the given soft deletion status
+    pub fn deleted(mut self, deleted: bool) -> Self {
+        self.deleted = Some(deleted);
+        self
+    }
+
+    /// Only matches users soft deleted at or before `instant`
+    pub fn deleted_before(mut self, instant: OffsetDateTime) -> Self {
+        self.deleted_before = Some(instant);
+        self
+    }
+
+    /// Returns `true` if the user satisfies the client-side part of the filter
+    pub fn matches(&self, user: &UserSchema) -> bool {
+        in_range(user.created_at, self.created_after, self.created_before)
+            && in_range(
+                user.last_sign_in_at,
+                self.last_sign_in_after,
+                self.last_sign_in_before,
+            )
+            && self
+                .email_confirmed
+                .is_none_or(|confirmed| user.email_confirmed_at.is_some() == confirmed)
+            && self
+                .deleted
+                .is_none_or(|deleted| user.deleted_at.is_some() == deleted)
+            && in_range(user.deleted_at, None, self.deleted_before)
+    }
+}
+
+fn in_range(
+    value: Option<OffsetDateTime>,
+    after: Option<OffsetDateTime>,
+    before: Option<OffsetDateTime>,
+) -> bool {
+    if after.is_none() && before.is_none() {
+        return true;
+    }
+    let Some(value) = value else {
+        return false;
+    };
+    after.is_none_or(|after| value >= after) && before.is_none_or(|before| value <= before)
+}
+
+/// Auth activity aggregated over a time window, see [`crate::AuthClient::admin_auth_stats`]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AuthStats {
+    /// Start of the window (inclusive)
+    #[serde(with = "time::serde::rfc3339")]
+    pub since: OffsetDateTime,
+    /// End of the window, the instant the stats were computed
+    #[serde(with = "time::serde::rfc3339")]
+    pub until: OffsetDateTime,
+    /// Users created during the window
+    pub signups: u64,
+    /// Users that confirmed their email address or phone number during the window
+    pub confirmations: u64,
+    /// Sign-ins recorded in the audit log during the window
+    pub sign_ins: u64,
+    /// Distinct users that signed in or refreshed a token during the window
+    pub active_sessions: u64,
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Claims of a Supabase Auth access token
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct Claims {
+    /// ID of the user the token was issued to
+    pub sub: Uuid,
+    /// Audience (typically `authenticated`)
+    pub aud: String,
+    /// Issuer, the auth URL of the project
+    pub iss: String,
+    /// Unix timestamp after which the token is invalid
+    pub exp: u64,
+    /// Unix timestamp at which the token was issued
+    pub iat: u64,
+    /// Postgres role the token grants (e.g. `authenticated`)
+    pub role: String,
+    /// Email address of the user
+    pub email: Option<String>,
+    /// Phone number of the user
+    pub phone: Option<String>,
+    /// ID of the session the token belongs to
+    pub session_id: Option<Uuid>,
+    /// Authenticator assurance level (`aal1` or `aal2`)
+    pub aal: Option<String>,
+    /// Methods the user authenticated with during the session
+    pub amr: Vec<AuthenticationMethod>,
+    /// Whether the user signed in anonymously
+    pub is_anonymous: bool,
+    /// App metadata of the user at the time the token was issued
+    pub app_metadata: HashMap<String, serde_json::Value>,
+    /// User metadata of the user at the time the token was issued
+    pub user_metadata: HashMap<String, serde_json::Value>,
+}
+
+impl Claims {
+    /// Returns `true` if the token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_in().is_zero()
+    }
+
+    /// Returns the time left until the token expires, or zero if it has expired
+    pub fn expires_in(&self) -> Duration {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+        Duration::from_secs(self.exp.saturating_sub(now))
+    }
+}
+
+/// Entry of the `amr` (authentication methods references) claim
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AuthenticationMethod {
+    /// Method used, e.g. `password`, `otp`, `oauth` or `totp`
+    pub method: String,
+    /// Unix timestamp at which the user authenticated with the method
+    pub timestamp: u64,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of MFA factor to enroll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MfaFactorType {
+    /// Time-based one-time password from an authenticator app
+    Totp,
+    /// One-time code sent by text message to `phone`
+    Phone {
+        /// Phone number receiving the codes
+        phone: String,
+    },
+}
+
+impl MfaFactorType {
+    /// Returns the factor type name used by GoTrue
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MfaFactorType::Totp => "totp",
+            MfaFactorType::Phone { .. } => "phone",
+        }
+    }
+}
+
+/// Factor created by [`AuthClient::mfa_enroll`](crate::AuthClient::mfa_enroll)
+///
+/// The factor stays unverified until a challenge is verified with
+/// [`AuthClient::mfa_verify`](crate::AuthClient::mfa_verify).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MfaEnrollResponse {
+    /// ID of the new factor
+    pub id: Uuid,
+    /// Factor type (`totp` or `phone`)
+    #[serde(rename = "type")]
+    pub factor_type: String,
+    /// User-friendly name of the factor
+    pub friendly_name: Option<String>,
+    /// Secret and QR code of TOTP factors
+    pub totp: Option<TotpEnrollment>,
+    /// Phone number of phone factors
+    pub phone: Option<String>,
+}
+
+/// Secret of a TOTP factor, to be added to an authenticator app
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TotpEnrollment {
+    /// QR code as an SVG data URI
+    pub qr_code: String,
+    /// Base32 encoded shared secret
+    pub secret: String,
+    /// `otpauth://` URI encoded in the QR code
+    pub uri: String,
+}
+
+/// Challenge created by [`AuthClient::mfa_challenge`](crate::AuthClient::mfa_challenge)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MfaChallengeResponse {
+    /// ID of the challenge, passed to `mfa_verify`
+    pub id: Uuid,
+    /// Factor type of the challenged factor
+    #[serde(rename = "type")]
+    pub factor_type: Option<String>,
+    /// Unix timestamp after which the challenge can no longer be verified
+    pub expires_at: u64,
+}
@@ -1,5 +1,27 @@
 //! Data models for the Supabase Auth API
 
+/// Admin API request models
+pub mod admin;
+/// Access token claims
+pub mod claims;
+/// Captured GoTrue responses for compatibility tests
+pub mod fixtures;
+/// Multi-factor authentication models
+pub mod mfa;
+/// OAuth sign-in models
+pub mod oauth;
+/// One-time password options
+pub mod otp;
+/// Password signup and sign-in options
+pub mod password;
+/// Authentication providers
+pub mod provider;
+/// Typed responses bundled with their raw JSON
+pub mod raw;
+/// Session models
+pub mod session;
+/// Auth server health and settings models
+pub mod settings;
 /// Token-related models
 pub mod token;
 /// User-related models
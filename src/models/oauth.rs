@@ -0,0 +1,104 @@
+/// Authorization URL of an OAuth sign-in, see
+/// [`AuthClient::get_oauth_authorize_url`](crate::AuthClient::get_oauth_authorize_url)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthAuthorizeUrl {
+    /// URL to redirect the user's browser to
+    pub url: String,
+    /// PKCE code verifier to keep until the returned code is exchanged with
+    /// [`AuthClient::exchange_code_for_session`](crate::AuthClient::exchange_code_for_session)
+    pub code_verifier: String,
+}
+
+/// Options for
+/// [`AuthClient::get_oauth_authorize_url_with_options`](crate::AuthClient::get_oauth_authorize_url_with_options)
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::oauth::OAuthOptions;
+///
+/// // Ask Google for a refresh token and let the user pick an account
+/// let options = OAuthOptions::new()
+///     .redirect_to("https://app.example.com/auth/callback")
+///     .query_param("access_type", "offline")
+///     .query_param("prompt", "consent select_account");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OAuthOptions {
+    /// URL to return to after sign-in; must be in the project's allow list
+    pub redirect_to: Option<String>,
+    /// Additional provider scopes to request
+    pub scopes: Vec<String>,
+    /// Additional query parameters passed on to the provider's authorization endpoint
+    pub query_params: Vec<(String, String)>,
+    /// Makes GoTrue respond with the provider URL as JSON instead of redirecting to it
+    pub skip_http_redirect: bool,
+}
+
+impl OAuthOptions {
+    /// Creates empty options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL to return to after sign-in
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Adds a provider scope to request
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scopes.push(scope.to_string());
+        self
+    }
+
+    /// Adds a query parameter passed on to the provider, e.g. `prompt=consent`
+    pub fn query_param(mut self, key: &str, value: &str) -> Self {
+        self.query_params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets whether GoTrue responds with the provider URL instead of redirecting to it
+    ///
+    /// Useful when the authorization URL is opened by a script rather than a browser.
+    pub fn skip_http_redirect(mut self, skip_http_redirect: bool) -> Self {
+        self.skip_http_redirect = skip_http_redirect;
+        self
+    }
+}
+
+/// Options for [`AuthClient::link_identity`](crate::AuthClient::link_identity)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkIdentityOptions {
+    /// URL to return to after linking; must be in the project's allow list
+    pub redirect_to: Option<String>,
+    /// Additional provider scopes to request
+    pub scopes: Vec<String>,
+    /// Additional query parameters passed on to the provider's authorization endpoint
+    pub query_params: Vec<(String, String)>,
+}
+
+impl LinkIdentityOptions {
+    /// Creates empty options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL to return to after linking
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Adds a provider scope to request
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scopes.push(scope.to_string());
+        self
+    }
+    /// Adds a query parameter passed on to the provider, e.g. `prompt=consent`
+    pub fn query_param(mut self, key: &str, value: &str) -> Self {
+        self.query_params.push((key.to_string(), value.to_string()));
+        self
+    }
+}
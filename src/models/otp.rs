@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::pkce::CodeChallenge;
+
+/// Options for [`AuthClient::signin_with_otp`](crate::AuthClient::signin_with_otp)
+///
+/// The length and lifetime of the sent code are configured on the server
+/// (`GOTRUE_MAILER_OTP_LENGTH`, `GOTRUE_SMS_OTP_LENGTH` and their `_EXP` counterparts).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtpOptions {
+    /// Create the user if no account exists for the email or phone number
+    ///
+    /// Defaults to `true`. When `false`, unknown users are rejected with
+    /// `AuthError::UserNotFound` and no code is sent.
+    pub should_create_user: bool,
+    /// URL the magic link redirects to after verification
+    pub email_redirect_to: Option<String>,
+    /// Metadata stored on the user if the user is created
+    pub data: Option<HashMap<String, serde_json::Value>>,
+    /// Channel the code is sent over to phone numbers
+    pub channel: OtpChannel,
+    /// Captcha token, if captcha protection is enabled
+    pub captcha_token: Option<String>,
+    /// Code challenge making the magic link use the PKCE flow, see [`crate::pkce`]
+    pub code_challenge: Option<CodeChallenge>,
+}
+
+impl Default for OtpOptions {
+    fn default() -> Self {
+        Self {
+            should_create_user: true,
+            email_redirect_to: None,
+            data: None,
+            channel: OtpChannel::default(),
+            captcha_token: None,
+            code_challenge: None,
+        }
+    }
+}
+
+impl OtpOptions {
+    /// Creates the default options, which create unknown users
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether unknown users are created
+    pub fn should_create_user(mut self, should_create_user: bool) -> Self {
+        self.should_create_user = should_create_user;
+        self
+    }
+
+    /// Sets the URL the magic link redirects to
+    pub fn email_redirect_to(mut self, url: &str) -> Self {
+        self.email_redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Sets the metadata stored on created users
+    pub fn data(mut self, data: HashMap<String, serde_json::Value>) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the channel the code is sent over to phone numbers
+    pub fn channel(mut self, channel: OtpChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.captcha_token = Some(token.to_string());
+        self
+    }
+
+    /// Sets the code challenge making the magic link use the PKCE flow
+    pub fn code_challenge(mut self, challenge: CodeChallenge) -> Self {
+        self.code_challenge = Some(challenge);
+        self
+    }
+}
+
+/// Options for [`AuthClient::reset_password_for_email`](crate::AuthClient::reset_password_for_email)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecoveryOptions {
+    /// URL the recovery link redirects to after verification
+    pub redirect_to: Option<String>,
+    /// Code challenge making the recovery link use the PKCE flow, see [`crate::pkce`]
+    pub code_challenge: Option<CodeChallenge>,
+    /// Captcha token, if captcha protection is enabled
+    pub captcha_token: Option<String>,
+}
+
+impl RecoveryOptions {
+    /// Creates empty options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL the recovery link redirects to
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Sets the code challenge making the recovery link use the PKCE flow
+    pub fn code_challenge(mut self, challenge: CodeChallenge) -> Self {
+        self.code_challenge = Some(challenge);
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.captcha_token = Some(token.to_string());
+        self
+    }
+}
+
+/// Channel one-time codes are sent over to phone numbers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpChannel {
+    /// Text message
+    #[default]
+    Sms,
+    /// WhatsApp message
+    Whatsapp,
+}
+
+/// Kind of one-time code or link being verified
+///
+/// Emails use the type of the flow that sent them; codes sent to phone numbers are
+/// verified as [`OtpType::Sms`] (or [`OtpType::PhoneChange`] for a number change).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtpType {
+    /// Signup confirmation
+    Signup,
+    /// Invitation
+    Invite,
+    /// Magic link or code sent by `signin_with_otp`
+    Magiclink,
+    /// Password recovery
+    Recovery,
+    /// Email address change
+    EmailChange,
+    /// Any email code; accepted by GoTrue in place of the more specific email types
+    Email,
+    /// Code sent to a phone number
+    Sms,
+    /// Phone number change
+    PhoneChange,
+}
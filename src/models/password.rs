@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use crate::models::otp::OtpChannel;
+use crate::models::token::TokenResponse;
+use crate::models::user::UserSchema;
+use crate::pkce::CodeChallenge;
+
+/// Options for [`AuthClient::signup_with_options`](crate::AuthClient::signup_with_options)
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::password::SignupOptions;
+///
+/// let options = SignupOptions::new()
+///     .metadata("full_name", "Jane Doe")
+///     .redirect_to("https://app.example.com/welcome");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignupOptions {
+    /// Metadata stored on the new user
+    pub data: Option<HashMap<String, serde_json::Value>>,
+    /// URL the confirmation link redirects to after verification
+    pub redirect_to: Option<String>,
+    /// Channel the confirmation code is sent over to phone numbers
+    ///
+    /// The server default (SMS) is used when `None`.
+    pub channel: Option<OtpChannel>,
+    /// Captcha token, if captcha protection is enabled
+    pub captcha_token: Option<String>,
+    /// Code challenge making the confirmation link use the PKCE flow, see [`crate::pkce`]
+    pub code_challenge: Option<CodeChallenge>,
+}
+
+impl SignupOptions {
+    /// Creates empty options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the metadata stored on the new user
+    pub fn data(mut self, data: HashMap<String, serde_json::Value>) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Adds a metadata entry stored on the new user
+    pub fn metadata(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.data
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Sets the URL the confirmation link redirects to
+    pub fn redirect_to(mut self, url: &str) -> Self {
+        self.redirect_to = Some(url.to_string());
+        self
+    }
+
+    /// Sets the channel the confirmation code is sent over to phone numbers
+    pub fn channel(mut self, channel: OtpChannel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.captcha_token = Some(token.to_string());
+        self
+    }
+
+    /// Sets the code challenge making the confirmation link use the PKCE flow
+    pub fn code_challenge(mut self, challenge: CodeChallenge) -> Self {
+        self.code_challenge = Some(challenge);
+        self
+    }
+}
+
+/// Outcome of [`AuthClient::signup_with_session`](crate::AuthClient::signup_with_session)
+///
+/// GoTrue signs new users in right away unless the project requires them to confirm their
+/// email address or phone number first, in which case only the unconfirmed user is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::password::SignupResult;
+///
+/// fn describe(result: &SignupResult) -> &'static str {
+///     match result {
+///         SignupResult::Session(_) => "signed in",
+///         SignupResult::ConfirmationRequired(_) => "check your inbox",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignupResult {
+    /// The user was created and signed in
+    Session(TokenResponse),
+    /// The user was created but has to confirm the email address or phone number first
+    ConfirmationRequired(UserSchema),
+}
+
+impl SignupResult {
+    /// Returns the new user
+    pub fn user(&self) -> Option<&UserSchema> {
+        match self {
+            SignupResult::Session(token_response) => token_response.user.as_ref(),
+            SignupResult::ConfirmationRequired(user) => Some(user),
+        }
+    }
+
+    /// Returns the session, if the user was signed in
+    pub fn session(&self) -> Option<&TokenResponse> {
+        match self {
+            SignupResult::Session(token_response) => Some(token_response),
+            SignupResult::ConfirmationRequired(_) => None,
+        }
+    }
+
+    /// Consumes the result, returning the session if the user was signed in
+    pub fn into_session(self) -> Option<TokenResponse> {
+        match self {
+            SignupResult::Session(token_response) => Some(token_response),
+            SignupResult::ConfirmationRequired(_) => None,
+        }
+    }
+
+    /// Returns `true` if the user has to confirm the email address or phone number first
+    pub fn is_confirmation_required(&self) -> bool {
+        matches!(self, SignupResult::ConfirmationRequired(_))
+    }
+}
+
+/// Options for
+/// [`AuthClient::signin_with_password_options`](crate::AuthClient::signin_with_password_options)
+///
+/// Options of passwordless sign-ins, such as whether unknown users are created, are set
+/// with [`OtpOptions`](crate::models::otp::OtpOptions).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigninOptions {
+    /// Captcha token, if captcha protection is enabled
+    pub captcha_token: Option<String>,
+}
+
+impl SigninOptions {
+    /// Creates empty options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the captcha token
+    pub fn captcha_token(mut self, token: &str) -> Self {
+        self.captcha_token = Some(token.to_string());
+        self
+    }
+}
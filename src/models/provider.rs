@@ -0,0 +1,165 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Authentication provider, as named by GoTrue
+///
+/// Providers unknown to this crate are kept in [`Provider::Other`] so new or custom
+/// providers round-trip unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Provider {
+    /// Email and password, magic link or email OTP
+    Email,
+    /// Phone number with SMS or WhatsApp OTP
+    Phone,
+    /// Anonymous sign-in
+    Anonymous,
+    /// Apple
+    Apple,
+    /// Azure (Microsoft)
+    Azure,
+    /// Bitbucket
+    Bitbucket,
+    /// Discord
+    Discord,
+    /// Facebook
+    Facebook,
+    /// Figma
+    Figma,
+    /// Fly.io
+    Fly,
+    /// GitHub
+    Github,
+    /// GitLab
+    Gitlab,
+    /// Google
+    Google,
+    /// Kakao
+    Kakao,
+    /// Keycloak
+    Keycloak,
+    /// LinkedIn (OpenID Connect)
+    LinkedinOidc,
+    /// Notion
+    Notion,
+    /// Slack (OpenID Connect)
+    SlackOidc,
+    /// Spotify
+    Spotify,
+    /// Twitch
+    Twitch,
+    /// Twitter
+    Twitter,
+    /// WorkOS
+    Workos,
+    /// Zoom
+    Zoom,
+    /// Any other provider, e.g. a SAML SSO provider (`sso:<id>`)
+    Other(String),
+}
+
+impl Provider {
+    /// Returns the provider name used by GoTrue
+    pub fn as_str(&self) -> &str {
+        match self {
+            Provider::Email => "email",
+            Provider::Phone => "phone",
+            Provider::Anonymous => "anonymous",
+            Provider::Apple => "apple",
+            Provider::Azure => "azure",
+            Provider::Bitbucket => "bitbucket",
+            Provider::Discord => "discord",
+            Provider::Facebook => "facebook",
+            Provider::Figma => "figma",
+            Provider::Fly => "fly",
+            Provider::Github => "github",
+            Provider::Gitlab => "gitlab",
+            Provider::Google => "google",
+            Provider::Kakao => "kakao",
+            Provider::Keycloak => "keycloak",
+            Provider::LinkedinOidc => "linkedin_oidc",
+            Provider::Notion => "notion",
+            Provider::SlackOidc => "slack_oidc",
+            Provider::Spotify => "spotify",
+            Provider::Twitch => "twitch",
+            Provider::Twitter => "twitter",
+            Provider::Workos => "workos",
+            Provider::Zoom => "zoom",
+            Provider::Other(name) => name,
+        }
+    }
+
+    /// Returns whether GoTrue accepts ID tokens issued by this provider
+    ///
+    /// See [`AuthClient::signin_with_id_token`](crate::AuthClient::signin_with_id_token).
+    pub fn supports_id_token(&self) -> bool {
+        matches!(
+            self,
+            Provider::Apple
+                | Provider::Azure
+                | Provider::Facebook
+                | Provider::Google
+                | Provider::Kakao
+                | Provider::Keycloak
+        )
+    }
+}
+
+impl From<&str> for Provider {
+    fn from(name: &str) -> Self {
+        match name {
+            "email" => Provider::Email,
+            "phone" => Provider::Phone,
+            "anonymous" => Provider::Anonymous,
+            "apple" => Provider::Apple,
+            "azure" => Provider::Azure,
+            "bitbucket" => Provider::Bitbucket,
+            "discord" => Provider::Discord,
+            "facebook" => Provider::Facebook,
+            "figma" => Provider::Figma,
+            "fly" => Provider::Fly,
+            "github" => Provider::Github,
+            "gitlab" => Provider::Gitlab,
+            "google" => Provider::Google,
+            "kakao" => Provider::Kakao,
+            "keycloak" => Provider::Keycloak,
+            "linkedin_oidc" => Provider::LinkedinOidc,
+            "notion" => Provider::Notion,
+            "slack_oidc" => Provider::SlackOidc,
+            "spotify" => Provider::Spotify,
+            "twitch" => Provider::Twitch,
+            "twitter" => Provider::Twitter,
+            "workos" => Provider::Workos,
+            "zoom" => Provider::Zoom,
+            other => Provider::Other(other.to_string()),
+        }
+    }
+}
+
+impl FromStr for Provider {
+    type Err = std::convert::Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(Provider::from(name))
+    }
+}
+
+impl Display for Provider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Provider {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Provider {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Provider::from(name.as_str()))
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// A typed response together with the raw JSON it was parsed from
+///
+/// Returned by the `*_with_raw` methods of [`AuthClient`](crate::AuthClient) so fields that
+/// are not modelled by this crate yet remain accessible.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Raw<T> {
+    /// The parsed response
+    pub value: T,
+    /// The response body as returned by the server
+    pub raw: serde_json::Value,
+}
+
+impl<T> Raw<T> {
+    /// Discards the raw JSON and returns the parsed response
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
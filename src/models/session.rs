@@ -0,0 +1,290 @@
+use std::time::Duration;
+
+use base64::alphabet::URL_SAFE;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, URL_SAFE_NO_PAD};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use reqwest::header::HeaderValue;
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::error::AuthError;
+use crate::headers::bearer_header_value;
+use crate::models::token::{TokenResponse, WeakPasswordError};
+use crate::models::user::UserSchema;
+
+/// Prefix of base64url encoded cookie values written by `@supabase/ssr`
+const COOKIE_BASE64_PREFIX: &str = "base64-";
+
+/// Longest cookie value `@supabase/ssr` writes before splitting it into chunks
+pub const MAX_COOKIE_CHUNK_SIZE: usize = 3180;
+
+/// Decodes base64url with or without padding, as written by different supabase-js versions
+const COOKIE_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// An authenticated user session
+///
+/// Contains the tokens of a signed-in user together with the absolute expiry of the
+/// access token.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Session {
+    /// JWT access token for API authentication
+    pub access_token: String,
+    /// Token type (typically "bearer")
+    pub token_type: String,
+    /// Token validity duration in seconds
+    pub expires_in: u64,
+    /// Unix timestamp when the access token expires
+    pub expires_at: u64,
+    /// Refresh token for obtaining new access tokens
+    pub refresh_token: String,
+    /// User information associated with the session
+    pub user: Option<UserSchema>,
+    /// OAuth provider token (if using third-party auth)
+    pub provider_token: Option<String>,
+    /// OAuth provider refresh token (if using third-party auth)
+    pub provider_refresh_token: Option<String>,
+    /// Weak password warning returned when the session was created
+    pub weak_password: Option<WeakPasswordError>,
+    /// Whether `expires_at` was derived from `expires_in` because the server omitted it
+    ///
+    /// Not serialized; converting the session back into a [`TokenResponse`] omits a
+    /// derived `expires_at` again.
+    #[serde(skip)]
+    pub expires_at_derived: bool,
+}
+
+impl Session {
+    /// Returns the `Authorization` header value (`Bearer <access_token>`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the access token is not a valid header value.
+    pub fn authorization_header(&self) -> Result<HeaderValue, AuthError> {
+        bearer_header_value(&self.access_token)
+    }
+
+    /// Adds the access token as a bearer `Authorization` header to `request`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::Session;
+    /// # async fn example(session: Session) -> Result<(), reqwest::Error> {
+    /// let todos = session
+    ///     .apply_to(reqwest::Client::new().get("https://your-project.supabase.co/rest/v1/todos"))
+    ///     .header("apikey", "your-anon-key")
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_to(&self, request: RequestBuilder) -> RequestBuilder {
+        request.bearer_auth(&self.access_token)
+    }
+
+    /// Returns `true` if the access token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::ZERO)
+    }
+
+    /// Returns `true` if the access token expires within `margin`
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+        now + margin.as_secs() >= self.expires_at
+    }
+
+    /// Returns `true` if the access token expired more than `idle_timeout` ago
+    ///
+    /// Sessions in use are refreshed around the expiry of their access token, so this
+    /// tells how long a session has gone unused. An expired access token alone does not
+    /// end a session: its refresh token stays valid until GoTrue's inactivity timeout or
+    /// time-box ends it.
+    pub fn idle_for(&self, idle_timeout: Duration) -> bool {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+        now >= self.expires_at.saturating_add(idle_timeout.as_secs())
+    }
+
+    /// Encodes the session as a cookie value in the format of `@supabase/ssr`
+    ///
+    /// The value is `base64-` followed by the base64url encoded session JSON, which
+    /// supabase-js clients read back. Values longer than [`MAX_COOKIE_CHUNK_SIZE`] have to
+    /// be split with [`Session::to_cookie_chunks`] to fit into browser cookie limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if the session cannot be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::Session;
+    ///
+    /// let session = Session {
+    ///     access_token: "jwt".to_string(),
+    ///     refresh_token: "refresh".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let value = session.to_cookie_value()?;
+    /// assert!(value.starts_with("base64-"));
+    /// assert_eq!(Session::from_cookie_value(&value)?, session);
+    /// # Ok::<(), supabase_auth_redux::AuthError>(())
+    /// ```
+    pub fn to_cookie_value(&self) -> Result<String, AuthError> {
+        let json = match serde_json::to_vec(self) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        Ok(format!(
+            "{}{}",
+            COOKIE_BASE64_PREFIX,
+            URL_SAFE_NO_PAD.encode(json)
+        ))
+    }
+
+    /// Encodes the session as cookie values of at most [`MAX_COOKIE_CHUNK_SIZE`] characters
+    ///
+    /// `@supabase/ssr` stores a single chunk under the cookie name itself (e.g.
+    /// `sb-<project_ref>-auth-token`) and several chunks under the name with `.0`, `.1`, ...
+    /// appended.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if the session cannot be serialized.
+    pub fn to_cookie_chunks(&self) -> Result<Vec<String>, AuthError> {
+        let value = self.to_cookie_value()?;
+        // The value is ASCII, so splitting bytes never splits a character
+        Ok(value
+            .as_bytes()
+            .chunks(MAX_COOKIE_CHUNK_SIZE)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect())
+    }
+
+    /// Decodes a session from a cookie value written by `@supabase/ssr` or
+    /// [`Session::to_cookie_value`]
+    ///
+    /// Accepts `base64-` prefixed values as well as the plain JSON written by older
+    /// `@supabase/ssr` versions. The value must already be percent-decoded, which cookie
+    /// parsers usually do.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the value is not an encoded session.
+    pub fn from_cookie_value(value: &str) -> Result<Session, AuthError> {
+        let json = match value.strip_prefix(COOKIE_BASE64_PREFIX) {
+            Some(encoded) => match COOKIE_BASE64.decode(encoded) {
+                Ok(json) => json,
+                Err(e) => {
+                    error!("invalid session cookie: {}", e);
+                    return Err(AuthError::InvalidParameters(None));
+                }
+            },
+            None => value.as_bytes().to_vec(),
+        };
+        match serde_json::from_slice::<Session>(&json) {
+            Ok(session) => Ok(session),
+            Err(e) => {
+                error!("invalid session cookie: {}", e);
+                Err(AuthError::InvalidParameters(None))
+            }
+        }
+    }
+
+    /// Decodes a session from the chunks of a cookie value, in order
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the joined chunks are not an encoded
+    /// session.
+    pub fn from_cookie_chunks<I, S>(chunks: I) -> Result<Session, AuthError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let value: String = chunks
+            .into_iter()
+            .map(|chunk| chunk.as_ref().to_string())
+            .collect();
+        Session::from_cookie_value(&value)
+    }
+}
+
+impl From<TokenResponse> for Session {
+    /// Builds a session from a token response, deriving `expires_at` if the server omitted it
+    fn from(token_response: TokenResponse) -> Self {
+        let expires_at = token_response
+            .expires_at
+            .filter(|expires_at| *expires_at > 0);
+        let expires_at_derived = expires_at.is_none();
+        let expires_at = expires_at.unwrap_or_else(|| {
+            time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64
+                + token_response.expires_in
+        });
+
+        Session {
+            access_token: token_response.access_token,
+            token_type: token_response.token_type,
+            expires_in: token_response.expires_in,
+            expires_at,
+            refresh_token: token_response.refresh_token,
+            user: token_response.user,
+            provider_token: token_response.provider_token,
+            provider_refresh_token: token_response.provider_refresh_token,
+            weak_password: token_response.weak_password,
+            expires_at_derived,
+        }
+    }
+}
+
+impl From<Session> for TokenResponse {
+    /// Converts a session back into a token response for functions still taking one
+    ///
+    /// Converting a `TokenResponse` into a `Session` and back yields the original value;
+    /// an `expires_at` derived from `expires_in` is omitted again.
+    fn from(session: Session) -> Self {
+        TokenResponse {
+            access_token: session.access_token,
+            token_type: session.token_type,
+            expires_in: session.expires_in,
+            expires_at: (!session.expires_at_derived).then_some(session.expires_at),
+            refresh_token: session.refresh_token,
+            user: session.user,
+            provider_token: session.provider_token,
+            provider_refresh_token: session.provider_refresh_token,
+            weak_password: session.weak_password,
+        }
+    }
+}
+
+/// Sessions ended by a sign-out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignOutScope {
+    /// Every session of the user
+    #[default]
+    Global,
+    /// Only the session of the access token
+    Local,
+    /// Every session of the user except the one of the access token
+    Others,
+}
+
+impl SignOutScope {
+    /// Returns the value of the `scope` query parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignOutScope::Global => "global",
+            SignOutScope::Local => "local",
+            SignOutScope::Others => "others",
+        }
+    }
+}
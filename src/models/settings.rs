@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::provider::Provider;
+
+/// Response of the auth server's health endpoint
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct HealthResponse {
+    /// GoTrue version (e.g. `v2.158.1`)
+    pub version: String,
+    /// Server name, `GoTrue`
+    pub name: String,
+    /// Server description
+    pub description: String,
+}
+
+/// Public settings of the auth server
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::provider::Provider;
+/// use supabase_auth_redux::models::settings::Settings;
+///
+/// let settings: Settings = serde_json::from_str(
+///     r#"{"external": {"email": true, "github": true, "google": false}, "disable_signup": false}"#,
+/// )?;
+/// assert!(settings.is_enabled(&Provider::Github));
+/// assert!(!settings.is_enabled(&Provider::Google));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Settings {
+    /// Whether each provider is enabled, keyed by provider name; includes `email`,
+    /// `phone` and `anonymous_users`
+    pub external: HashMap<String, bool>,
+    /// Whether new users can sign up
+    pub disable_signup: bool,
+    /// Whether email addresses are confirmed without sending a confirmation email
+    pub mailer_autoconfirm: bool,
+    /// Whether phone numbers are confirmed without sending a confirmation code
+    pub phone_autoconfirm: bool,
+    /// SMS provider sending phone codes (e.g. `twilio`)
+    pub sms_provider: Option<String>,
+    /// Whether SAML single sign-on is enabled
+    pub saml_enabled: bool,
+    /// Whether MFA is enabled, on GoTrue versions reporting it
+    pub mfa_enabled: Option<bool>,
+    /// Minimum password length, on GoTrue versions reporting it
+    pub password_min_length: Option<usize>,
+    /// Character sets a password must each contain one character of, separated by `:`
+    /// like GoTrue's `GOTRUE_PASSWORD_REQUIRED_CHARACTERS`, on GoTrue versions reporting it
+    pub password_required_characters: Option<String>,
+}
+
+impl Settings {
+    /// Returns whether a provider is enabled
+    pub fn is_enabled(&self, provider: &Provider) -> bool {
+        let name = match provider {
+            Provider::Anonymous => "anonymous_users",
+            provider => provider.as_str(),
+        };
+        self.external.get(name).copied().unwrap_or(false)
+    }
+
+    /// Returns every enabled provider
+    pub fn enabled_providers(&self) -> Vec<Provider> {
+        let mut providers: Vec<Provider> = self
+            .external
+            .iter()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(name, _)| match name.as_str() {
+                "anonymous_users" => Provider::Anonymous,
+                name => Provider::from(name),
+            })
+            .collect();
+        providers.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        providers
+    }
+}
@@ -1,11 +1,15 @@
+use reqwest::header::HeaderValue;
+use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
+use crate::error::AuthError;
+use crate::headers::bearer_header_value;
 use crate::models::user::UserSchema;
 
 /// Response containing authentication tokens and user information
 ///
 /// This is returned after successful authentication operations like signin or signup.
-#[derive(Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(default)]
 pub struct TokenResponse {
     /// JWT access token for API authentication
@@ -14,22 +18,59 @@ pub struct TokenResponse {
     pub token_type: String,
     /// Token validity duration in seconds
     pub expires_in: u64,
-    /// Unix timestamp when the token expires
-    pub expires_at: u64,
+    /// Unix timestamp when the token expires, if the server sent it
+    pub expires_at: Option<u64>,
     /// Refresh token for obtaining new access tokens
     pub refresh_token: String,
     /// User information associated with the token
     pub user: Option<UserSchema>,
     /// OAuth provider token (if using third-party auth)
-    pub provider_token: String,
+    pub provider_token: Option<String>,
     /// OAuth provider refresh token (if using third-party auth)
-    pub provider_refresh_token: String,
+    pub provider_refresh_token: Option<String>,
     /// Weak password warning information
     pub weak_password: Option<WeakPasswordError>,
 }
 
+impl TokenResponse {
+    /// Returns the `Authorization` header value (`Bearer <access_token>`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the access token is not a valid header value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::TokenResponse;
+    /// # fn example(tokens: TokenResponse) -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = reqwest::Client::new()
+    ///     .get("https://your-project.supabase.co/rest/v1/todos")
+    ///     .header(reqwest::header::AUTHORIZATION, tokens.bearer()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bearer(&self) -> Result<HeaderValue, AuthError> {
+        bearer_header_value(&self.access_token)
+    }
+
+    /// Returns the time at which the access token expires, if the server sent it
+    ///
+    /// Use [`Session::from`](crate::Session) to derive the expiry from `expires_in` when
+    /// `expires_at` is missing.
+    pub fn expires_at_datetime(&self) -> Option<time::OffsetDateTime> {
+        let expires_at = i64::try_from(self.expires_at?).ok()?;
+        time::OffsetDateTime::from_unix_timestamp(expires_at).ok()
+    }
+
+    /// Adds the access token as a bearer `Authorization` header to `request`
+    pub fn apply_to(&self, request: RequestBuilder) -> RequestBuilder {
+        request.bearer_auth(&self.access_token)
+    }
+}
+
 /// Error information returned when a password is considered weak
-#[derive(Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(default)]
 pub struct WeakPasswordError {
     /// Description of why the password is weak
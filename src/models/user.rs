@@ -1,8 +1,13 @@
 use std::collections::HashMap;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::otp::OtpChannel;
+use crate::models::provider::Provider;
+use crate::models::session::Session;
+
 /// Represents a user in the Supabase Auth system
 ///
 /// This struct contains all the information about a user including their
@@ -62,7 +67,7 @@ pub struct UserSchema {
     /// Multi-factor authentication factors
     pub factors: Vec<MFAFactorSchema>,
     /// OAuth/social login identities linked to this user
-    pub identities: Option<Vec<HashMap<String, serde_json::Value>>>,
+    pub identities: Option<Vec<IdentitySchema>>,
     /// Timestamp until which the user is banned
     #[serde(with = "time::serde::rfc3339::option")]
     pub banned_until: Option<time::OffsetDateTime>,
@@ -77,21 +82,365 @@ pub struct UserSchema {
     pub updated_at: Option<time::OffsetDateTime>,
 }
 
+/// An identity linked to a user, such as an email address or a GitHub account
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct IdentitySchema {
+    /// Unique identifier of the identity, used to unlink it; sent by GoTrue v2.138 and later
+    #[serde(default)]
+    pub identity_id: Option<Uuid>,
+    /// Identifier of the user at the provider (e.g. the GitHub user ID)
+    #[serde(default)]
+    pub id: String,
+    /// The user the identity belongs to
+    #[serde(default)]
+    pub user_id: Uuid,
+    /// Provider of the identity
+    pub provider: Provider,
+    /// Profile data reported by the provider (e.g. `email`, `name`, `avatar_url`)
+    #[serde(default)]
+    pub identity_data: Option<HashMap<String, serde_json::Value>>,
+    /// Email address of the identity, if any
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Timestamp of the last sign in with the identity
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub last_sign_in_at: Option<time::OffsetDateTime>,
+    /// Timestamp when the identity was linked
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+    /// Timestamp when the identity was last updated
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+impl UserSchema {
+    /// Returns the provider the user signed up with (`app_metadata.provider`)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::models::provider::Provider;
+    /// use supabase_auth_redux::User;
+    ///
+    /// let user: User = serde_json::from_str(
+    ///     r#"{"app_metadata": {"provider": "github", "providers": ["github", "email"]}}"#,
+    /// )?;
+    /// assert_eq!(user.provider(), Some(Provider::Github));
+    /// assert_eq!(user.providers(), vec![Provider::Github, Provider::Email]);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn provider(&self) -> Option<Provider> {
+        self.app_metadata
+            .as_ref()?
+            .get("provider")?
+            .as_str()
+            .map(Provider::from)
+    }
+
+    /// Returns whether the user is banned at `now`, i.e. `banned_until` lies after it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::User;
+    /// use time::OffsetDateTime;
+    ///
+    /// let user: User = serde_json::from_str(r#"{"banned_until": "2100-01-01T00:00:00Z"}"#)?;
+    /// assert!(user.is_banned_at(OffsetDateTime::now_utc()));
+    /// assert!(!User::default().is_banned_at(OffsetDateTime::now_utc()));
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn is_banned_at(&self, now: time::OffsetDateTime) -> bool {
+        self.banned_until
+            .is_some_and(|banned_until| banned_until > now)
+    }
+
+    /// Returns whether the user is currently banned
+    pub fn is_banned(&self) -> bool {
+        self.is_banned_at(time::OffsetDateTime::now_utc())
+    }
+
+    /// Returns every provider linked to the user (`app_metadata.providers`)
+    ///
+    /// Returns an empty list if the field is missing or malformed.
+    pub fn providers(&self) -> Vec<Provider> {
+        self.app_metadata
+            .as_ref()
+            .and_then(|app_metadata| app_metadata.get("providers"))
+            .and_then(|providers| providers.as_array())
+            .map(|providers| {
+                providers
+                    .iter()
+                    .filter_map(|provider| provider.as_str())
+                    .map(Provider::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Deserializes `user_metadata` into a caller-provided type
+    ///
+    /// Missing metadata is treated as an empty object, so types whose fields all have
+    /// defaults deserialize successfully for users without metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json::Error` if the metadata does not match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use supabase_auth_redux::User;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Profile {
+    ///     full_name: String,
+    ///     #[serde(default)]
+    ///     newsletter: bool,
+    /// }
+    ///
+    /// let user: User = serde_json::from_str(r#"{"user_metadata": {"full_name": "Jane Doe"}}"#)?;
+    /// let profile: Profile = user.user_metadata_as()?;
+    /// assert_eq!(profile.full_name, "Jane Doe");
+    /// assert!(!profile.newsletter);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn user_metadata_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        metadata_as(self.user_metadata.as_ref())
+    }
+
+    /// Deserializes `app_metadata` into a caller-provided type
+    ///
+    /// Behaves like [`UserSchema::user_metadata_as`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json::Error` if the metadata does not match `T`.
+    pub fn app_metadata_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        metadata_as(self.app_metadata.as_ref())
+    }
+
+    /// Returns the first identity of the user with `provider`, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::models::provider::Provider;
+    /// use supabase_auth_redux::User;
+    ///
+    /// let user: User = serde_json::from_str(
+    ///     r#"{"identities": [{"id": "583231", "provider": "github", "identity_data": {"user_name": "octocat"}}]}"#,
+    /// )?;
+    /// let github = user.identity(&Provider::Github).unwrap();
+    /// assert_eq!(github.id, "583231");
+    /// assert!(user.identity(&Provider::Google).is_none());
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn identity(&self, provider: &Provider) -> Option<&IdentitySchema> {
+        self.identities
+            .as_deref()?
+            .iter()
+            .find(|identity| identity.provider == *provider)
+    }
+
+    /// Returns the email address change awaiting confirmation, if any
+    ///
+    /// Set by [`AuthClient::request_email_change`](crate::AuthClient::request_email_change)
+    /// and cleared once the change is confirmed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::User;
+    ///
+    /// let user: User = serde_json::from_str(
+    ///     r#"{"email": "old@example.com", "new_email": "new@example.com", "email_change_sent_at": "2024-05-01T12:00:00Z"}"#,
+    /// )?;
+    /// let pending = user.pending_email_change().unwrap();
+    /// assert_eq!(pending.value, "new@example.com");
+    /// assert!(pending.sent_at.is_some());
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn pending_email_change(&self) -> Option<PendingChange> {
+        pending_change(self.new_email.as_deref(), self.email_change_sent_at)
+    }
+
+    /// Returns the phone number change awaiting confirmation, if any
+    ///
+    /// Set by [`AuthClient::request_phone_change`](crate::AuthClient::request_phone_change)
+    /// and cleared once the change is confirmed.
+    pub fn pending_phone_change(&self) -> Option<PendingChange> {
+        pending_change(self.new_phone.as_deref(), self.phone_change_sent_at)
+    }
+}
+
+/// An email address or phone number change awaiting confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingChange {
+    /// The new email address or phone number
+    pub value: String,
+    /// When the confirmation was sent
+    pub sent_at: Option<time::OffsetDateTime>,
+}
+
+fn pending_change(
+    value: Option<&str>,
+    sent_at: Option<time::OffsetDateTime>,
+) -> Option<PendingChange> {
+    let value = value.filter(|value| !value.is_empty())?;
+    Some(PendingChange {
+        value: value.to_string(),
+        sent_at,
+    })
+}
+
+/// Outcome of confirming an email address change
+///
+/// With secure email change enabled on the project, GoTrue sends a confirmation to both the
+/// current and the new address, and the change only takes effect once both are confirmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmailChangeVerification {
+    /// One confirmation was accepted; the user still has to confirm the other address
+    AwaitingOtherConfirmation {
+        /// The server's message, e.g. asking to confirm the link sent to the other address
+        message: String,
+    },
+    /// The change took effect and the user is signed in
+    Completed(Box<Session>),
+}
+
+impl IdentitySchema {
+    /// Deserializes `identity_data` into a caller-provided type
+    ///
+    /// Behaves like [`UserSchema::user_metadata_as`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json::Error` if the identity data does not match `T`.
+    pub fn identity_data_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        metadata_as(self.identity_data.as_ref())
+    }
+}
+
+fn metadata_as<T: DeserializeOwned>(
+    metadata: Option<&HashMap<String, serde_json::Value>>,
+) -> Result<T, serde_json::Error> {
+    let object = metadata
+        .map(|metadata| metadata.clone().into_iter().collect())
+        .unwrap_or_default();
+    serde_json::from_value(serde_json::Value::Object(object))
+}
+
+/// Attributes changed by [`AuthClient::update_user`](crate::AuthClient::update_user)
+///
+/// Attributes left as `None` are not changed. Changing the email address or phone number
+/// sends a confirmation to the new address and only takes effect once it is confirmed.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::models::user::UserUpdateParams;
+///
+/// let params = UserUpdateParams::new()
+///     .email("new@example.com")
+///     .data("full_name", "Jane Doe");
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct UserUpdateParams {
+    /// New email address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// New phone number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// Channel the phone change code is sent over; the server default (SMS) is used
+    /// when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<OtpChannel>,
+    /// New password
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Reauthentication code, required to change the password when secure password
+    /// change is enabled; sent by
+    /// [`AuthClient::reauthenticate`](crate::AuthClient::reauthenticate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// User metadata entries to set; other entries are kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<HashMap<String, serde_json::Value>>,
+    /// URL the email change confirmation link redirects to
+    #[serde(skip)]
+    pub email_redirect_to: Option<String>,
+}
+
+impl UserUpdateParams {
+    /// Creates parameters changing nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes the email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = Some(email.to_string());
+        self
+    }
+
+    /// Changes the phone number
+    pub fn phone(mut self, phone_number: &str) -> Self {
+        self.phone = Some(phone_number.to_string());
+        self
+    }
+
+    /// Sets the channel the phone change code is sent over
+    pub fn channel(mut self, channel: OtpChannel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Changes the password
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    /// Sets the reauthentication code
+    pub fn nonce(mut self, nonce: &str) -> Self {
+        self.nonce = Some(nonce.to_string());
+        self
+    }
+
+    /// Sets a user metadata entry
+    pub fn data(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.data
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Sets the URL the email change confirmation link redirects to
+    pub fn email_redirect_to(mut self, url: &str) -> Self {
+        self.email_redirect_to = Some(url.to_string());
+        self
+    }
+}
+
 /// Multi-factor authentication factor information
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct MFAFactorSchema {
     /// Type of MFA factor (e.g., "totp")
-    factor_type: Option<String>,
+    pub factor_type: Option<String>,
     /// User-friendly name for the factor
-    friendly_name: Option<String>,
+    pub friendly_name: Option<String>,
     /// Unique identifier for the factor
-    id: Option<Uuid>,
+    pub id: Option<Uuid>,
     /// Verification status of the factor
-    status: Option<MFAFactorStatus>,
+    pub status: Option<MFAFactorStatus>,
 }
 
 /// Status of a multi-factor authentication factor
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum MFAFactorStatus {
     /// Factor has been verified and is active
     Verified,
@@ -0,0 +1,48 @@
+//! Metrics of auth operations, emitted through the `metrics` facade
+//!
+//! Enabled with the `metrics` feature. Every operation records:
+//!
+//! * `supabase_auth_operations_total` - counter labelled with `operation`, `status`
+//!   (`success` or `failure`), `error` (the [`AuthErrorKind`](crate::AuthErrorKind), empty on
+//!   success) and `gotrue_error_code` (empty unless GoTrue sent one)
+//! * `supabase_auth_operation_duration_seconds` - histogram labelled with `operation` and
+//!   `status`
+//!
+//! Nothing is exported until the application installs a recorder, e.g. from
+//! `metrics-exporter-prometheus`.
+
+use std::time::Duration;
+
+use ::metrics::{counter, histogram};
+
+use crate::error::AuthError;
+
+/// Records the outcome and latency of an operation
+pub(crate) fn record_operation<T>(
+    operation: &'static str,
+    result: &Result<T, AuthError>,
+    latency: Duration,
+) {
+    let (status, error, gotrue_error_code) = match result {
+        Ok(_) => ("success", String::new(), String::new()),
+        Err(e) => (
+            "failure",
+            e.kind().to_string(),
+            e.error_code().unwrap_or_default().to_string(),
+        ),
+    };
+    counter!(
+        "supabase_auth_operations_total",
+        "operation" => operation,
+        "status" => status,
+        "error" => error,
+        "gotrue_error_code" => gotrue_error_code,
+    )
+    .increment(1);
+    histogram!(
+        "supabase_auth_operation_duration_seconds",
+        "operation" => operation,
+        "status" => status,
+    )
+    .record(latency.as_secs_f64());
+}
@@ -0,0 +1,271 @@
+//! Client-side password policy checks
+//!
+//! A [`PasswordPolicy`] mirrors the password requirements configured on the GoTrue server
+//! (minimum length and required character classes) so forms can reject weak passwords
+//! without a round trip. Failures use the same [`WeakPasswordError`] shape (`message` and
+//! `reasons`) as the server. Policies can be written by hand, parsed from GoTrue's
+//! configuration with [`PasswordPolicy::from_required_characters`], or read from servers
+//! reporting them with [`AuthClient::fetch_password_policy`].
+
+use tracing::{debug, info, instrument};
+
+use crate::error::AuthError;
+use crate::models::settings::Settings;
+use crate::models::token::WeakPasswordError;
+use crate::AuthClient;
+
+/// Lowercase letters, as listed by GoTrue
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+/// Uppercase letters, as listed by GoTrue
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Digits, as listed by GoTrue
+const DIGITS: &str = "0123456789";
+/// Symbols, as listed by GoTrue
+const SYMBOLS: &str = "!@#$%^&*()_+-=[]{};'\\:\"|<>?,./`~";
+
+/// Password requirements checked before passwords are sent to the server
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::password_policy::PasswordPolicy;
+///
+/// let policy = PasswordPolicy::new(8).require_digits(true);
+///
+/// assert!(policy.check("correct-horse-42").is_ok());
+///
+/// let weak = policy.check("short").unwrap_err();
+/// assert_eq!(weak.reasons, vec!["length", "characters"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum number of characters
+    pub min_length: usize,
+    /// Require at least one lowercase letter
+    pub require_lowercase: bool,
+    /// Require at least one uppercase letter
+    pub require_uppercase: bool,
+    /// Require at least one digit
+    pub require_digits: bool,
+    /// Require at least one symbol
+    pub require_symbols: bool,
+}
+
+impl Default for PasswordPolicy {
+    /// The GoTrue default: at least 6 characters, no required character classes
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
+impl PasswordPolicy {
+    /// Creates a policy requiring `min_length` characters and no character classes
+    pub fn new(min_length: usize) -> Self {
+        Self {
+            min_length,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digits: false,
+            require_symbols: false,
+        }
+    }
+
+    /// Creates a policy from GoTrue's password configuration
+    ///
+    /// `required_characters` uses the format of `GOTRUE_PASSWORD_REQUIRED_CHARACTERS`:
+    /// character sets separated by `:`, with `\:` escaping a colon inside a set. Each set
+    /// is mapped to the character class of its first character, so the sets of the Supabase
+    /// dashboard's "password requirements" option translate to the matching `require_*`
+    /// flags.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::password_policy::PasswordPolicy;
+    ///
+    /// let policy = PasswordPolicy::from_required_characters(
+    ///     8,
+    ///     "abcdefghijklmnopqrstuvwxyz:ABCDEFGHIJKLMNOPQRSTUVWXYZ:0123456789",
+    /// );
+    /// assert!(policy.require_lowercase && policy.require_uppercase && policy.require_digits);
+    /// assert!(!policy.require_symbols);
+    /// ```
+    pub fn from_required_characters(min_length: usize, required_characters: &str) -> Self {
+        let mut policy = Self::new(min_length);
+        for set in split_character_sets(required_characters) {
+            match set.chars().next() {
+                Some(c) if LOWERCASE.contains(c) => policy.require_lowercase = true,
+                Some(c) if UPPERCASE.contains(c) => policy.require_uppercase = true,
+                Some(c) if DIGITS.contains(c) => policy.require_digits = true,
+                Some(_) => policy.require_symbols = true,
+                None => {}
+            }
+        }
+        policy
+    }
+
+    /// Creates a policy from the server's public settings
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the settings do not report a minimum password length; older GoTrue
+    /// versions do not.
+    pub fn from_settings(settings: &Settings) -> Option<Self> {
+        let min_length = settings.password_min_length?;
+        Some(Self::from_required_characters(
+            min_length,
+            settings
+                .password_required_characters
+                .as_deref()
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Sets whether a lowercase letter is required
+    pub fn require_lowercase(mut self, required: bool) -> Self {
+        self.require_lowercase = required;
+        self
+    }
+
+    /// Sets whether an uppercase letter is required
+    pub fn require_uppercase(mut self, required: bool) -> Self {
+        self.require_uppercase = required;
+        self
+    }
+
+    /// Sets whether a digit is required
+    pub fn require_digits(mut self, required: bool) -> Self {
+        self.require_digits = required;
+        self
+    }
+
+    /// Sets whether a symbol is required
+    pub fn require_symbols(mut self, required: bool) -> Self {
+        self.require_symbols = required;
+        self
+    }
+
+    /// Checks `password` against the policy
+    ///
+    /// # Errors
+    ///
+    /// Returns a `WeakPasswordError` with the reasons `length` and/or `characters`, matching
+    /// the server's response for the same password.
+    pub fn check(&self, password: &str) -> Result<(), WeakPasswordError> {
+        let mut reasons = Vec::new();
+        let mut messages = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            reasons.push("length".to_string());
+            messages.push(format!(
+                "Password should be at least {} characters.",
+                self.min_length
+            ));
+        }
+
+        let required_sets: Vec<&str> = [
+            (self.require_lowercase, LOWERCASE),
+            (self.require_uppercase, UPPERCASE),
+            (self.require_digits, DIGITS),
+            (self.require_symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter_map(|(required, set)| required.then_some(set))
+        .collect();
+        let missing_class = required_sets
+            .iter()
+            .any(|set| !password.chars().any(|c| set.contains(c)));
+        if missing_class {
+            reasons.push("characters".to_string());
+            messages.push(format!(
+                "Password should contain at least one character of each: {}.",
+                required_sets.join(", ")
+            ));
+        }
+
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        Err(WeakPasswordError {
+            message: messages.join(" "),
+            reasons,
+        })
+    }
+}
+
+/// Splits GoTrue's `:` separated character sets, honouring `\:` escapes
+fn split_character_sets(required_characters: &str) -> Vec<String> {
+    let mut sets = vec![String::new()];
+    let mut chars = required_characters.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&':') => {
+                if let Some(last) = sets.last_mut() {
+                    last.push(':');
+                }
+                chars.next();
+            }
+            ':' => sets.push(String::new()),
+            c => {
+                if let Some(last) = sets.last_mut() {
+                    last.push(c);
+                }
+            }
+        }
+    }
+    sets.retain(|set| !set.is_empty());
+    sets
+}
+
+impl AuthClient {
+    /// Reads the password policy from the server's public settings
+    ///
+    /// Pass the result to [`AuthClientBuilder::password_policy`](crate::AuthClientBuilder::password_policy)
+    /// to check passwords against the project's requirements before sending them.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the server does not report its password requirements.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`AuthClient::settings`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// if let Some(policy) = client.fetch_password_policy().await? {
+    ///     let client = AuthClient::builder()
+    ///         .api_url("https://your-project.supabase.co")
+    ///         .anon_key("your-anon-key")
+    ///         .password_policy(policy)
+    ///         .build()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context))]
+    pub async fn fetch_password_policy(&self) -> Result<Option<PasswordPolicy>, AuthError> {
+        let settings = self.settings().await?;
+        let policy = PasswordPolicy::from_settings(&settings);
+        debug!(?policy, "fetched password policy");
+        Ok(policy)
+    }
+
+    /// Checks `password` against the configured password policy, if any
+    pub(crate) fn check_password_policy(&self, password: &str) -> Result<(), AuthError> {
+        let Some(policy) = &self.password_policy else {
+            return Ok(());
+        };
+        if let Err(weak_password) = policy.check(password) {
+            info!(reasons = ?weak_password.reasons, "password rejected by client-side policy");
+            return Err(AuthError::WeakPassword {
+                reasons: weak_password.reasons,
+                api_error: None,
+            });
+        }
+        Ok(())
+    }
+}
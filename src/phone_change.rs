@@ -0,0 +1,115 @@
+use tracing::{error, info, instrument};
+
+use crate::error::{ApiError, AuthError};
+use crate::id_type::normalize_phone;
+use crate::models::otp::OtpType;
+use crate::models::session::Session;
+use crate::models::user::{UserSchema, UserUpdateParams};
+use crate::verify_otp::VerifyRequest;
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Starts changing the phone number of the user owning an access token
+    ///
+    /// GoTrue texts a code to the new number. The returned user lists the new number as
+    /// [`UserSchema::pending_phone_change`] until the code is confirmed with
+    /// [`AuthClient::verify_phone_change`]. To send the code over WhatsApp, call
+    /// [`AuthClient::update_user`] with [`UserUpdateParams::channel`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `new_phone` - The new phone number, normalized to E.164
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the new phone number is not valid.
+    /// Otherwise fails like [`AuthClient::update_user`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = client
+    ///     .request_phone_change(access_token, "+1 415 555 0100")
+    ///     .await?;
+    /// if let Some(pending) = user.pending_phone_change() {
+    ///     println!("Code sent to {} at {:?}", pending.value, pending.sent_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_phone_change(
+        &self,
+        access_token: &str,
+        new_phone: &str,
+    ) -> Result<UserSchema, AuthError> {
+        let new_phone = normalize_phone(new_phone)?;
+        self.update_user(access_token, UserUpdateParams::new().phone(&new_phone))
+            .await
+    }
+
+    /// Confirms a phone number change with the code texted to the new number
+    ///
+    /// The new number is read from the pending change of the user owning the access
+    /// token. On success the change takes effect and the user is signed in again.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `otp` - The code from the text message
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the code is empty or the user has no
+    /// pending phone number change.
+    /// Returns `AuthError::NotAuthorized` if the access token or code is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// // The user enters the code from the text message
+    /// let session = client.verify_phone_change(access_token, "123456").await?;
+    /// println!("Phone changed, signed in until {}", session.expires_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn verify_phone_change(
+        &self,
+        access_token: &str,
+        otp: &str,
+    ) -> Result<Session, AuthError> {
+        self.observe("verify_phone_change", None, async {
+            if otp.is_empty() {
+                error!("empty token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let user = self.get_user_by_token(access_token).await?;
+            let Some(pending) = user.pending_phone_change() else {
+                error!("no pending phone change");
+                return Err(AuthError::InvalidParameters(Some(Box::new(
+                    ApiError::validation("no phone number change is pending".to_string()),
+                ))));
+            };
+
+            let body = VerifyRequest {
+                otp_type: OtpType::PhoneChange,
+                token: Some(otp),
+                token_hash: None,
+                email: None,
+                phone: Some(&pending.value),
+            };
+            let token_response = self.verify_request(&body).await?;
+            info!("phone change completed");
+
+            Ok(Session::from(token_response))
+        })
+        .await
+    }
+}
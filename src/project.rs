@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use postgrest::Postgrest;
+use tracing::error;
+use url::Url;
+
+use crate::error::AuthError;
+use crate::failover::ApiUrls;
+use crate::{AuthClient, AuthClientBuilder};
+
+/// Length of a Supabase project reference
+const PROJECT_REF_LEN: usize = 20;
+
+impl AuthClient {
+    /// Creates a client for a project hosted on supabase.com
+    ///
+    /// The API URL is derived from the project reference as `https://<project_ref>.supabase.co`.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_ref` - The project reference, as shown in the dashboard URL and settings
+    /// * `anon_key` - The anonymous key for your Supabase project
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the project reference is not 20 lowercase
+    /// letters and digits, or the anon key is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::for_project("abcdefghijklmnopqrst", "your-anon-key")?;
+    /// # Ok::<(), supabase_auth_redux::AuthError>(())
+    /// ```
+    pub fn for_project(project_ref: &str, anon_key: &str) -> Result<Self, AuthError> {
+        AuthClient::new(&project_url(project_ref)?, anon_key)
+    }
+
+    /// Creates a client for a project served from a custom domain
+    ///
+    /// `domain` may be given as a bare host name (`auth.example.com`) or as an `https` URL;
+    /// any path is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the domain is not a valid host name or
+    /// `https` origin, or the anon key is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use supabase_auth_redux::AuthClient;
+    ///
+    /// let client = AuthClient::for_custom_domain("auth.example.com", "your-anon-key")?;
+    /// # Ok::<(), supabase_auth_redux::AuthError>(())
+    /// ```
+    pub fn for_custom_domain(domain: &str, anon_key: &str) -> Result<Self, AuthError> {
+        AuthClient::new(&custom_domain_url(domain)?, anon_key)
+    }
+
+    /// Returns a client for another project that shares this client's HTTP connection pool
+    ///
+    /// Meant for multi-tenant services talking to many projects: build one client with the
+    /// shared configuration (timeouts, retry policy, hooks, redaction, auth path) and derive a client per
+    /// project instead of constructing a full client, with its own connection pool, for
+    /// every tenant. The derived client has no fallback URLs and no JWT verifier, since both
+    /// belong to a single project.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_url` - The base URL of the project
+    /// * `anon_key` - The anonymous key of the project
+    /// * `service_role_key` - The service role key of the project, for admin operations
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the API URL or anon key is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(shared: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let tenant_client = shared.with_project(
+    ///     "https://tenant-project.supabase.co",
+    ///     "tenant-anon-key",
+    ///     None,
+    /// )?;
+    /// let user = tenant_client.get_user_by_token(access_token).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_project(
+        &self,
+        api_url: &str,
+        anon_key: &str,
+        service_role_key: Option<&str>,
+    ) -> Result<AuthClient, AuthError> {
+        if api_url.is_empty() || anon_key.is_empty() {
+            error!("project requires an api url and anon key");
+            return Err(AuthError::InvalidParameters(None));
+        }
+
+        Ok(AuthClient {
+            api_urls: Arc::new(ApiUrls::new(
+                vec![api_url.to_owned()],
+                self.api_urls.auth_path(),
+            )),
+            supabase_anon_key: anon_key.to_owned(),
+            supabase_service_role_key: service_role_key.map(str::to_owned),
+            postgrest_client: Postgrest::new(format!("{}/rest/v1/", api_url))
+                .schema("auth")
+                .insert_header("apikey", anon_key),
+            jwt_verifier: None,
+            refresh_flights: Arc::default(),
+            ..self.clone()
+        })
+    }
+
+    /// Returns a client for a project hosted on supabase.com that shares this client's HTTP
+    /// connection pool
+    ///
+    /// See [`AuthClient::with_project`] and [`AuthClient::for_project`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the project reference is not 20 lowercase
+    /// letters and digits, or the anon key is empty.
+    pub fn with_project_ref(
+        &self,
+        project_ref: &str,
+        anon_key: &str,
+        service_role_key: Option<&str>,
+    ) -> Result<AuthClient, AuthError> {
+        self.with_project(&project_url(project_ref)?, anon_key, service_role_key)
+    }
+}
+
+impl AuthClientBuilder {
+    /// Sets the API URL to `https://<project_ref>.supabase.co`
+    ///
+    /// An invalid project reference is reported by [`AuthClientBuilder::build`].
+    pub fn project_ref(mut self, project_ref: &str) -> Self {
+        self.api_url = project_url(project_ref).ok();
+        self
+    }
+}
+
+/// Returns the API URL of a project hosted on supabase.com
+fn project_url(project_ref: &str) -> Result<String, AuthError> {
+    let valid = project_ref.len() == PROJECT_REF_LEN
+        && project_ref
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if !valid {
+        error!("invalid project ref: {:?}", project_ref);
+        return Err(AuthError::InvalidParameters(None));
+    }
+    Ok(format!("https://{}.supabase.co", project_ref))
+}
+
+/// Returns the API URL of a project served from a custom domain
+fn custom_domain_url(domain: &str) -> Result<String, AuthError> {
+    let domain = domain.trim().trim_end_matches('/');
+    let with_scheme = if domain.contains("://") {
+        domain.to_string()
+    } else {
+        format!("https://{}", domain)
+    };
+
+    let url = match Url::parse(&with_scheme) {
+        Ok(url) => url,
+        Err(e) => {
+            error!("invalid custom domain {:?}: {}", domain, e);
+            return Err(AuthError::InvalidParameters(None));
+        }
+    };
+    if url.scheme() != "https"
+        || url.host_str().is_none()
+        || url.path() != "/"
+        || url.query().is_some()
+    {
+        error!("custom domain must be an https origin: {:?}", domain);
+        return Err(AuthError::InvalidParameters(None));
+    }
+    Ok(url.origin().ascii_serialization())
+}
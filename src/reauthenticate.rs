@@ -0,0 +1,84 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::util::{error_with_body, handle_response};
+use crate::{AuthClient, AuthError};
+
+impl AuthClient {
+    /// Sends a reauthentication code to the email address or phone number of a user
+    ///
+    /// Projects with secure password change enabled require recently signed-in users to
+    /// confirm a password change with this code, passed to
+    /// [`AuthClient::update_user`] as the
+    /// [`nonce`](crate::models::user::UserUpdateParams::nonce).
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token is empty or the user has neither
+    /// an email address nor a phone number.
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::user::UserUpdateParams;
+    /// # async fn example(client: AuthClient, access_token: &str, code: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// client.reauthenticate(access_token).await?;
+    ///
+    /// // The user enters the code they received
+    /// client
+    ///     .update_user(
+    ///         access_token,
+    ///         UserUpdateParams::new().password("new-password").nonce(code),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn reauthenticate(&self, access_token: &str) -> Result<(), AuthError> {
+        self.observe("reauthenticate", None, async {
+            if access_token.is_empty() {
+                error!("empty token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .get(format!("{}/reauthenticate", api_url))
+                        .bearer_auth(access_token)
+                        .header("apiKey", &self.supabase_anon_key)
+                })
+                .instrument(trace_span!("gotrue reauthenticate"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+            info!("sent reauthentication code");
+
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,106 @@
+//! Redaction of secrets and personal data in logs
+//!
+//! Operations log tokens, email addresses, phone numbers and response bodies at debug
+//! level and on their tracing spans. The [`RedactionPolicy`] set with
+//! [`AuthClientBuilder::redaction`](crate::AuthClientBuilder::redaction) decides how much
+//! of these values reaches the logs.
+
+use serde_json::Value;
+
+use crate::util::{mask_identifier, mask_token};
+
+/// Placeholder logged in place of fully redacted values
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Fields of GoTrue responses holding tokens
+const TOKEN_FIELDS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "provider_token",
+    "provider_refresh_token",
+    "hashed_token",
+    "email_otp",
+];
+
+/// Fields of GoTrue responses holding email addresses or phone numbers
+const IDENTIFIER_FIELDS: &[&str] = &["email", "phone", "new_email", "new_phone"];
+
+/// How much of tokens and user identifiers is logged
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::RedactionPolicy;
+///
+/// let token = "eyJhbGciOiJIUzI1NiJ9.e30.c2lnbmF0dXJl";
+/// assert_eq!(RedactionPolicy::Full.token(token), "[REDACTED]");
+/// assert_eq!(RedactionPolicy::Partial.token(token), "eyJh•••dXJl");
+/// assert_eq!(RedactionPolicy::None.token(token), token);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedactionPolicy {
+    /// Replaces tokens, email addresses and phone numbers with `[REDACTED]`
+    Full,
+    /// Keeps a few characters of each value to tell them apart (`eyJh•••dXJl`,
+    /// `t***@example.com`); the default
+    #[default]
+    Partial,
+    /// Logs values unchanged; only for local debugging
+    None,
+}
+
+impl RedactionPolicy {
+    /// Redacts a token
+    pub fn token(&self, token: &str) -> String {
+        match self {
+            RedactionPolicy::Full => REDACTED.to_string(),
+            RedactionPolicy::Partial => mask_token(token),
+            RedactionPolicy::None => token.to_string(),
+        }
+    }
+
+    /// Redacts an email address or phone number
+    pub fn identifier(&self, value: &str) -> String {
+        match self {
+            RedactionPolicy::Full => REDACTED.to_string(),
+            RedactionPolicy::Partial => mask_identifier(value),
+            RedactionPolicy::None => value.to_string(),
+        }
+    }
+
+    /// Redacts the token and identifier fields of a JSON response body, at any depth
+    ///
+    /// Bodies that are not JSON are returned unchanged.
+    pub fn response(&self, resp_text: &str) -> String {
+        if *self == RedactionPolicy::None {
+            return resp_text.to_string();
+        }
+        let Ok(mut body) = serde_json::from_str::<Value>(resp_text) else {
+            return resp_text.to_string();
+        };
+        self.redact_value(&mut body);
+        body.to_string()
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (name, field) in fields.iter_mut() {
+                    match field {
+                        Value::String(text) if TOKEN_FIELDS.contains(&name.as_str()) => {
+                            *text = self.token(text);
+                        }
+                        Value::String(text)
+                            if IDENTIFIER_FIELDS.contains(&name.as_str()) && !text.is_empty() =>
+                        {
+                            *text = self.identifier(text);
+                        }
+                        _ => self.redact_value(field),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::error::AuthError;
+
+type Flight = Arc<OnceCell<Result<String, AuthError>>>;
+
+/// Refresh requests in flight, keyed by refresh token
+///
+/// With refresh token rotation, a refresh token can only be exchanged once; tasks
+/// refreshing the same token concurrently share the response of a single request instead
+/// of the later ones being rejected.
+#[derive(Debug, Default)]
+pub(crate) struct RefreshFlights {
+    flights: Mutex<HashMap<String, Flight>>,
+}
+
+impl RefreshFlights {
+    /// Runs `refresh` for `token`, or waits for the result of the refresh already in flight
+    pub(crate) async fn run<F>(&self, token: &str, refresh: F) -> Result<String, AuthError>
+    where
+        F: Future<Output = Result<String, AuthError>>,
+    {
+        let flight = self
+            .flights
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(token.to_string())
+            .or_default()
+            .clone();
+
+        // If the task running the request is cancelled, a waiting task takes over
+        let result = flight.get_or_init(|| refresh).await.clone();
+
+        let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+        if flights
+            .get(token)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            flights.remove(token);
+        }
+        result
+    }
+}
@@ -1,9 +1,11 @@
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use tracing::{debug, error, info, instrument};
 
 use crate::error::AuthError;
+use crate::models::raw::Raw;
 use crate::models::token::TokenResponse;
-use crate::util::handle_response_code;
+use crate::util::{parse_with_raw, Credentials, Endpoint};
 use crate::AuthClient;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +28,9 @@ impl AuthClient {
     ///
     /// Returns a `TokenResponse` containing new access and refresh tokens.
     ///
+    /// Concurrent calls with the same refresh token send a single request and share its
+    /// result, so rotating the token cannot make the other calls fail.
+    ///
     /// # Errors
     ///
     /// Returns `AuthError::InvalidParameters` if the token is empty.
@@ -48,64 +53,83 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self))]
+    #[instrument(skip(self, token), fields(token = %self.redaction.token(token), tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn refresh_token(&self, token: &str) -> Result<TokenResponse, AuthError> {
+        self.observe("refresh_token", None, async {
+            let resp_text = self.refresh_token_text(token).await?;
+
+            let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+                Ok(token_response) => token_response,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(
+                tokens_are_nonempty = !token_response.access_token.is_empty()
+                    && !token_response.refresh_token.is_empty()
+            );
+            debug!(
+                token = self.redaction.token(&token_response.access_token),
+                refresh_token = self.redaction.token(&token_response.refresh_token)
+            );
+
+            Ok(token_response)
+        })
+        .await
+    }
+
+    /// Refreshes an authentication token, keeping the raw response
+    ///
+    /// Behaves like [`AuthClient::refresh_token`], but also returns the raw JSON response
+    /// body so fields not modelled by `TokenResponse` remain accessible.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let new_tokens = client.refresh_token_with_raw("user-refresh-token").await?;
+    /// println!("Raw response: {}", new_tokens.raw);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, token), fields(token = %self.redaction.token(token), tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn refresh_token_with_raw(
+        &self,
+        token: &str,
+    ) -> Result<Raw<TokenResponse>, AuthError> {
+        self.observe("refresh_token", None, async {
+            let resp_text = self.refresh_token_text(token).await?;
+            parse_with_raw(&resp_text)
+        })
+        .await
+    }
+
+    async fn refresh_token_text(&self, token: &str) -> Result<String, AuthError> {
         if token.is_empty() {
             error!("empty token");
-            return Err(AuthError::InvalidParameters);
+            return Err(AuthError::InvalidParameters(None));
         }
 
-        let token_grant = TokenRefreshGrant {
-            refresh_token: token.to_string(),
-        };
-
-        let resp = match self
-            .http_client
-            .post(format!(
-                "{}/auth/v1/{}",
-                self.supabase_api_url, "token?grant_type=refresh_token"
-            ))
-            .bearer_auth(&self.supabase_anon_key)
-            .header("apiKey", &self.supabase_anon_key)
-            .json(&token_grant)
-            .send()
-            .instrument(trace_span!("gotrue refresh token"))
+        self.refresh_flights
+            .run(token, self.refresh_token_request(token))
             .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                log::error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result?;
+    }
 
-        let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
-            Ok(token_response) => token_response,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Internal);
-            }
+    async fn refresh_token_request(&self, token: &str) -> Result<String, AuthError> {
+        let token_grant = TokenRefreshGrant {
+            refresh_token: token.to_string(),
         };
-        info!(
-            tokens_are_nonempty =
-                !token_response.access_token.is_empty() && !token_response.refresh_token.is_empty()
-        );
-        debug!(
-            token = token_response.access_token,
-            refresh_token = token_response.refresh_token
-        );
 
-        Ok(token_response)
+        self.execute_text(
+            Endpoint::new(Method::POST, "token", Credentials::Anon)
+                .query("grant_type", "refresh_token")
+                .json(&token_grant)
+                .retry(),
+        )
+        .await
     }
 }
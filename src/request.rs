@@ -0,0 +1,57 @@
+use reqwest::{Method, RequestBuilder};
+
+use crate::AuthClient;
+
+impl AuthClient {
+    /// Returns a pre-authenticated request to a GoTrue endpoint this crate does not wrap
+    ///
+    /// The request targets `path` below the auth path (`/auth/v1` unless configured with
+    /// [`AuthClientBuilder::auth_path`](crate::AuthClientBuilder::auth_path)) of the primary
+    /// API URL and carries the `apiKey` header and an `Authorization: Bearer` header set to
+    /// `access_token`, or to the anon key if it is `None`, as well as the `X-Client-Info`
+    /// header. It uses the client's HTTP client and request timeout,
+    /// but is sent once, without failover, retries or error mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method
+    /// * `path` - The endpoint path below the auth path, e.g. `"factors"`
+    /// * `access_token` - The access token of the user the request acts for
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), reqwest::Error> {
+    /// let resp = client
+    ///     .request(reqwest::Method::GET, "user", Some(access_token))
+    ///     .send()
+    ///     .await?
+    ///     .error_for_status()?;
+    /// let user: serde_json::Value = resp.json().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn request(
+        &self,
+        method: Method,
+        path: &str,
+        access_token: Option<&str>,
+    ) -> RequestBuilder {
+        let url = format!(
+            "{}/{}",
+            self.api_urls.primary(),
+            path.trim_start_matches('/')
+        );
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .bearer_auth(access_token.unwrap_or(&self.supabase_anon_key))
+            .header("apiKey", &self.supabase_anon_key)
+            .header("X-Client-Info", &self.client_info);
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+}
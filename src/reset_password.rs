@@ -0,0 +1,122 @@
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::otp::RecoveryOptions;
+use crate::pkce::CodeChallenge;
+use crate::util::{error_with_body, handle_response, mask_identifier, MetaSecurity};
+use crate::AuthClient;
+
+#[derive(Debug, Serialize)]
+struct RecoverRequest<'a> {
+    email: &'a str,
+    #[serde(flatten)]
+    code_challenge: Option<&'a CodeChallenge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gotrue_meta_security: Option<MetaSecurity<'a>>,
+}
+
+impl AuthClient {
+    /// Sends a password recovery email
+    ///
+    /// The email contains a link signing the user in, after which the password can be
+    /// changed with [`AuthClient::update_user`]. GoTrue does not reveal whether an account
+    /// exists, so unknown addresses succeed as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - The email address of the account
+    /// * `options` - Redirect URL, PKCE code challenge and captcha token
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the email is empty or rejected by the server.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::otp::RecoveryOptions;
+    /// use supabase_auth_redux::pkce::{generate_code_verifier, CodeChallenge};
+    ///
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// // Keep the verifier to exchange the `code` of the redirect for a session
+    /// let code_verifier = generate_code_verifier();
+    /// client
+    ///     .reset_password_for_email(
+    ///         "user@example.com",
+    ///         RecoveryOptions::new()
+    ///             .redirect_to("https://app.example.com/reset-password")
+    ///             .code_challenge(CodeChallenge::from_verifier(&code_verifier)),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn reset_password_for_email(
+        &self,
+        email: &str,
+        options: RecoveryOptions,
+    ) -> Result<(), AuthError> {
+        self.observe(
+            "reset_password_for_email",
+            Some(mask_identifier(email)),
+            async {
+                let email = self.normalize_email(email.to_string());
+                if email.is_empty() {
+                    error!("empty email");
+                    return Err(AuthError::InvalidParameters(None));
+                }
+
+                let body = RecoverRequest {
+                    email: &email,
+                    code_challenge: options.code_challenge.as_ref(),
+                    gotrue_meta_security: MetaSecurity::from_token(
+                        options.captcha_token.as_deref(),
+                    ),
+                };
+
+                let resp = match self
+                    .send_with_failover(|api_url| {
+                        let mut request = self
+                            .http_client
+                            .post(format!("{}/recover", api_url))
+                            .bearer_auth(&self.supabase_anon_key)
+                            .header("apiKey", &self.supabase_anon_key)
+                            .json(&body);
+                        if let Some(redirect_to) = self.redirect_url(options.redirect_to.as_deref())
+                        {
+                            request = request.query(&[("redirect_to", redirect_to)]);
+                        }
+                        request
+                    })
+                    .instrument(trace_span!("gotrue recover"))
+                    .await
+                {
+                    Ok(resp) => resp,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+
+                let resp_code_result = handle_response(&resp).await;
+                let resp_text = match resp.text().await {
+                    Ok(resp_text) => resp_text,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(AuthError::Http);
+                    }
+                };
+                debug!("resp_text: {}", self.redaction.response(&resp_text));
+                resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+                info!("recovery email sent");
+                Ok(())
+            },
+        )
+        .await
+    }
+}
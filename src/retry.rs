@@ -0,0 +1,183 @@
+//! Retries of transient failures
+//!
+//! A [`RetryPolicy`] configured with
+//! [`AuthClientBuilder::retry_policy`](crate::AuthClientBuilder::retry_policy) retries
+//! idempotent operations (`get_user_by_token`, `refresh_token`, admin user listings,
+//! `health` and `settings`) when GoTrue cannot be reached, times out or answers with a
+//! transient status code. Other operations are never retried, since repeating them could
+//! e.g. send a second email.
+//!
+//! Rate limited operations fail with `AuthError::RateLimited`, whose
+//! [`retry_after`](crate::AuthError::retry_after) tells callers how long to wait.
+
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response};
+use tracing::warn;
+
+use crate::util::retry_after;
+use crate::AuthClient;
+
+/// Status codes retried by default: 408, 429 and the transient 5xx codes
+const DEFAULT_RETRY_ON_STATUS: &[u16] = &[408, 429, 500, 502, 503, 504];
+
+/// How often and how quickly transient failures are retried
+///
+/// The delay before retry `n` is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`.
+/// With jitter enabled, a random delay between half and all of that is used so clients
+/// failing together do not retry in lockstep.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use supabase_auth_redux::retry::RetryPolicy;
+/// use supabase_auth_redux::AuthClient;
+///
+/// let client = AuthClient::builder()
+///     .api_url("https://your-project.supabase.co")
+///     .anon_key("your-anon-key")
+///     .retry_policy(
+///         RetryPolicy::new(4)
+///             .initial_backoff(Duration::from_millis(200))
+///             .retry_on_status(&[502, 503, 504]),
+///     )
+///     .build()
+///     .expect("Failed to create auth client");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between attempts
+    pub max_backoff: Duration,
+    /// Randomize delays between half and all of the computed backoff
+    pub jitter: bool,
+    /// Response status codes that are retried
+    pub retry_on_status: Vec<u16>,
+    /// Wait for the delay of a `Retry-After` header instead of the computed backoff
+    pub honor_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting at 100ms and backing off up to 2s with jitter
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy making at most `max_attempts` attempts with the default backoff
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            jitter: true,
+            retry_on_status: DEFAULT_RETRY_ON_STATUS.to_vec(),
+            honor_retry_after: false,
+        }
+    }
+
+    /// Sets the delay before the first retry
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the upper bound of the delay between attempts
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets whether delays are randomized
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the response status codes that are retried
+    pub fn retry_on_status(mut self, statuses: &[u16]) -> Self {
+        self.retry_on_status = statuses.to_vec();
+        self
+    }
+
+    /// Sets whether the delay of a `Retry-After` response header replaces the computed
+    /// backoff
+    ///
+    /// Rate limited (429) and unavailable (503) responses may carry the header. The
+    /// server's delay is used even if it exceeds `max_backoff`.
+    pub fn honor_retry_after(mut self, honor: bool) -> Self {
+        self.honor_retry_after = honor;
+        self
+    }
+
+    /// Returns the delay before retry number `retry`, starting at 1
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        if !self.jitter {
+            return backoff;
+        }
+
+        let mut bytes = [0u8; 4];
+        if getrandom::getrandom(&mut bytes).is_err() {
+            return backoff;
+        }
+        let fraction = f64::from(u32::from_le_bytes(bytes)) / f64::from(u32::MAX);
+        backoff.mul_f64(0.5 + fraction / 2.0)
+    }
+
+    fn should_retry(&self, result: &Result<Response, reqwest::Error>) -> bool {
+        match result {
+            Ok(resp) => self.retry_on_status.contains(&resp.status().as_u16()),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        }
+    }
+}
+
+impl AuthClient {
+    /// Sends an idempotent request, retrying transient failures per the retry policy
+    ///
+    /// Each attempt goes through [`AuthClient::send_with_failover`]. Without a retry
+    /// policy the request is sent once. The last attempt's result is returned as is.
+    pub(crate) async fn send_with_retry(
+        &self,
+        request: impl Fn(&str) -> RequestBuilder,
+    ) -> Result<Response, reqwest::Error> {
+        let Some(policy) = &self.retry_policy else {
+            return self.send_with_failover(&request).await;
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = self.send_with_failover(&request).await;
+            if attempt >= policy.max_attempts || !policy.should_retry(&result) {
+                return result;
+            }
+
+            let server_delay = match &result {
+                Ok(resp) if policy.honor_retry_after => retry_after(resp.headers()),
+                _ => None,
+            };
+            let backoff = server_delay.unwrap_or_else(|| policy.backoff(attempt));
+            match &result {
+                Ok(resp) => warn!(
+                    attempt,
+                    status = resp.status().as_u16(),
+                    ?backoff,
+                    "retrying"
+                ),
+                Err(e) => warn!(attempt, ?backoff, "retrying: {}", e),
+            }
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
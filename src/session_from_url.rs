@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use tracing::{debug, error, instrument};
+use url::Url;
+
+use crate::error::AuthError;
+use crate::models::session::Session;
+
+/// Authentication result carried by an auth redirect URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlSession {
+    /// Tokens delivered in the URL fragment by the implicit flow
+    Session(Box<Session>),
+    /// Authorization code delivered in the query string by the PKCE flow, ready to be
+    /// exchanged for a session
+    Code(String),
+}
+
+/// Extracts the session or PKCE authorization code from an auth redirect URL
+///
+/// This mirrors `detectSessionInUrl` of supabase-js for server-side callback handlers:
+///
+/// * Implicit flow redirects carry `access_token`, `refresh_token`, `expires_in` and
+///   optionally `expires_at` in the URL fragment and produce a [`UrlSession::Session`].
+///   `expires_at` is derived from `expires_in` when missing. The session's `user` is not
+///   part of the URL and is left empty.
+/// * PKCE flow redirects carry a `code` query parameter and produce a [`UrlSession::Code`].
+///
+/// # Errors
+///
+/// Returns `AuthError::NotAuthorized` if the URL carries an `error` from the auth server
+/// (e.g. an expired link).
+/// Returns `AuthError::InvalidParameters` if the URL cannot be parsed or carries neither
+/// tokens nor a code.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::{parse_session_from_url, UrlSession};
+///
+/// let url = "https://example.com/callback#access_token=jwt&refresh_token=abc&expires_in=3600&expires_at=1700003600&token_type=bearer";
+/// match parse_session_from_url(url)? {
+///     UrlSession::Session(session) => assert_eq!(session.refresh_token, "abc"),
+///     UrlSession::Code(_) => unreachable!(),
+/// }
+/// # Ok::<(), supabase_auth_redux::AuthError>(())
+/// ```
+#[instrument(skip_all)]
+pub fn parse_session_from_url(url: &str) -> Result<UrlSession, AuthError> {
+    let url = match Url::parse(url) {
+        Ok(url) => url,
+        Err(e) => {
+            error!("{}", e);
+            return Err(AuthError::InvalidParameters(None));
+        }
+    };
+
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let fragment: HashMap<String, String> = url
+        .fragment()
+        .map(|fragment| {
+            url::form_urlencoded::parse(fragment.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(error) = fragment.get("error").or_else(|| query.get("error")) {
+        let description = fragment
+            .get("error_description")
+            .or_else(|| query.get("error_description"));
+        debug!(
+            error,
+            error_description = description,
+            "auth redirect error"
+        );
+        return Err(AuthError::NotAuthorized(None));
+    }
+
+    if let Some(access_token) = fragment.get("access_token") {
+        let Some(refresh_token) = fragment.get("refresh_token") else {
+            error!("missing refresh_token");
+            return Err(AuthError::InvalidParameters(None));
+        };
+        let expires_in = parse_number(&fragment, "expires_in")?.unwrap_or_default();
+        let expires_at = parse_number(&fragment, "expires_at")?;
+        let expires_at_derived = expires_at.is_none();
+        let expires_at = match expires_at {
+            Some(expires_at) => expires_at,
+            None => time::OffsetDateTime::now_utc().unix_timestamp() as u64 + expires_in,
+        };
+
+        return Ok(UrlSession::Session(Box::new(Session {
+            access_token: access_token.clone(),
+            token_type: fragment
+                .get("token_type")
+                .cloned()
+                .unwrap_or_else(|| "bearer".to_string()),
+            expires_in,
+            expires_at,
+            refresh_token: refresh_token.clone(),
+            user: None,
+            provider_token: fragment.get("provider_token").cloned(),
+            provider_refresh_token: fragment.get("provider_refresh_token").cloned(),
+            weak_password: None,
+            expires_at_derived,
+        })));
+    }
+
+    if let Some(code) = query.get("code") {
+        return Ok(UrlSession::Code(code.clone()));
+    }
+
+    debug!("no session or code in url");
+    Err(AuthError::InvalidParameters(None))
+}
+
+fn parse_number(params: &HashMap<String, String>, key: &str) -> Result<Option<u64>, AuthError> {
+    params
+        .get(key)
+        .map(|value| {
+            value.parse::<u64>().map_err(|e| {
+                error!("invalid {}: {}", key, e);
+                AuthError::InvalidParameters(None)
+            })
+        })
+        .transpose()
+}
@@ -0,0 +1,464 @@
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, warn};
+
+use crate::error::AuthError;
+use crate::models::session::Session;
+use crate::session_store::SessionStore;
+use crate::AuthClient;
+
+/// Re-acquires credentials when a session can no longer be refreshed
+///
+/// Long-running daemons register a provider on their [`SessionManager`] so a revoked or
+/// expired refresh token does not require a restart: the provider can prompt the user, read
+/// fresh credentials from a secrets manager or run a device flow and sign in again.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use async_trait::async_trait;
+/// use supabase_auth_redux::{AuthClient, AuthError, CredentialProvider, IdType, Session};
+///
+/// struct EnvCredentials;
+///
+/// #[async_trait]
+/// impl CredentialProvider for EnvCredentials {
+///     async fn acquire(&self, client: &AuthClient) -> Result<Session, AuthError> {
+///         let email = std::env::var("SERVICE_EMAIL").map_err(|_| AuthError::InvalidParameters(None))?;
+///         let password =
+///             std::env::var("SERVICE_PASSWORD").map_err(|_| AuthError::InvalidParameters(None))?;
+///         let tokens = client
+///             .signin_with_password(IdType::Email(email), password)
+///             .await?;
+///         Ok(tokens.into())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Produces a new session after refreshing the current one failed permanently
+    async fn acquire(&self, client: &AuthClient) -> Result<Session, AuthError>;
+}
+
+/// Maximum number of superseded refresh tokens remembered per session
+pub(crate) const MAX_SUPERSEDED_TOKENS: usize = 32;
+
+/// Report of a superseded refresh token being presented again
+///
+/// GoTrue rotates the refresh token on every refresh. A rotated-out token showing up again
+/// means two parties hold the same session, which usually means the token was stolen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenReuseEvent {
+    /// Key of the session in the session store, if the manager persists to one
+    pub session_key: Option<String>,
+    /// Number of rotations since the reused token was superseded (1 for the previous token)
+    pub rotations_since: usize,
+}
+
+/// Callback receiving token reuse events
+type TokenReuseHook =
+    Arc<dyn Fn(TokenReuseEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// Current session together with the refresh tokens it superseded
+struct ManagedSession {
+    /// The current session
+    session: Session,
+    /// Refresh tokens rotated out of this session, oldest first
+    superseded: Vec<String>,
+}
+
+impl ManagedSession {
+    /// Replaces the session, remembering the refresh token it rotated out
+    fn rotate(&mut self, session: Session) -> Option<String> {
+        let previous = std::mem::replace(&mut self.session, session);
+        if previous.refresh_token.is_empty() || previous.refresh_token == self.session.refresh_token
+        {
+            return None;
+        }
+        self.superseded.push(previous.refresh_token.clone());
+        if self.superseded.len() > MAX_SUPERSEDED_TOKENS {
+            self.superseded.remove(0);
+        }
+        Some(previous.refresh_token)
+    }
+}
+
+/// Keeps a session valid by refreshing its access token before it expires
+///
+/// All methods take `&self`; concurrent callers share one refresh through an internal
+/// mutex, so the manager can be wrapped in an `Arc` and used from many tasks.
+pub struct SessionManager {
+    /// Client used to refresh the session
+    client: AuthClient,
+    /// The current session and its refresh token family
+    state: Mutex<ManagedSession>,
+    /// How long before expiry the access token is refreshed
+    refresh_margin: Duration,
+    /// Fallback invoked when the refresh token is no longer accepted
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    /// Background task pruning expired sessions from a session store
+    pruning_task: Option<JoinHandle<()>>,
+    /// Store the session is persisted to after every change, and its key
+    session_store: Option<(Arc<dyn SessionStore>, String)>,
+    /// Callback notified when a superseded refresh token is presented again
+    token_reuse_hook: Option<TokenReuseHook>,
+}
+
+impl Debug for SessionManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionManager")
+    }
+}
+
+impl Drop for SessionManager {
+    fn drop(&mut self) {
+        if let Some(pruning_task) = self.pruning_task.take() {
+            pruning_task.abort();
+        }
+    }
+}
+
+impl SessionManager {
+    /// Default time before expiry at which the access token is refreshed
+    pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+    /// Creates a manager for `session`, refreshing it with `client`
+    pub fn new(client: AuthClient, session: Session) -> Self {
+        Self {
+            client,
+            state: Mutex::new(ManagedSession {
+                session,
+                superseded: Vec::new(),
+            }),
+            refresh_margin: Self::DEFAULT_REFRESH_MARGIN,
+            credential_provider: None,
+            pruning_task: None,
+            session_store: None,
+            token_reuse_hook: None,
+        }
+    }
+
+    /// Sets how long before expiry the access token is refreshed
+    pub fn with_refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Registers a provider used to re-acquire credentials when refreshing fails permanently
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Persists the session to `store` under `key` after every refresh
+    ///
+    /// Superseded refresh tokens are recorded with
+    /// [`SessionStore::record_superseded`], so reuse is detected across restarts and across
+    /// processes sharing the store.
+    pub fn with_session_store(mut self, store: Arc<dyn SessionStore>, key: &str) -> Self {
+        self.session_store = Some((store, key.to_string()));
+        self
+    }
+
+    /// Registers an async callback notified when a superseded refresh token is presented
+    ///
+    /// The callback runs before [`SessionManager::set_session`] returns
+    /// `AuthError::RefreshTokenReused`, making it the place to revoke the session or alert.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, Session, SessionManager};
+    /// # fn example(client: AuthClient, session: Session) {
+    /// let manager = SessionManager::new(client, session).on_token_reuse(|event| async move {
+    ///     eprintln!(
+    ///         "refresh token reused {} rotations after it was superseded",
+    ///         event.rotations_since
+    ///     );
+    /// });
+    /// # }
+    /// ```
+    pub fn on_token_reuse<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(TokenReuseEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.token_reuse_hook = Some(Arc::new(move |event| Box::pin(callback(event))));
+        self
+    }
+
+    /// Prunes expired sessions from `store` every `interval` while the manager is alive
+    ///
+    /// Which sessions count as expired is up to the store, see
+    /// [`SessionStore::prune_expired`].
+    /// The pruning task is spawned on the current Tokio runtime and stopped when the
+    /// manager is dropped. Calling this again replaces the previous task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// # use supabase_auth_redux::{AuthClient, MemorySessionStore, Session, SessionManager};
+    /// # async fn example(client: AuthClient, session: Session) {
+    /// let store = Arc::new(MemorySessionStore::new());
+    /// let manager = SessionManager::new(client, session)
+    ///     .with_store_pruning(store, Duration::from_secs(15 * 60));
+    /// # }
+    /// ```
+    pub fn with_store_pruning(mut self, store: Arc<dyn SessionStore>, interval: Duration) -> Self {
+        if let Some(previous) = self.pruning_task.take() {
+            previous.abort();
+        }
+        self.pruning_task = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = store.prune_expired().await {
+                    error!("pruning expired sessions failed: {}", e);
+                }
+            }
+        }));
+        self
+    }
+
+    /// Returns a copy of the current session
+    pub async fn session(&self) -> Session {
+        self.state.lock().await.session.clone()
+    }
+
+    /// Returns the refresh tokens rotated out of the session, oldest first
+    pub async fn superseded_refresh_tokens(&self) -> Vec<String> {
+        self.state.lock().await.superseded.clone()
+    }
+
+    /// Replaces the session, e.g. with one a client sent back after refreshing on its own
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::RefreshTokenReused` if the session's refresh token was already
+    /// rotated out, after notifying the token reuse callback. The current session is kept.
+    /// Returns the session store's error if persisting the session fails.
+    pub async fn set_session(&self, session: Session) -> Result<(), AuthError> {
+        let mut state = self.state.lock().await;
+
+        let mut rotations_since = state
+            .superseded
+            .iter()
+            .rposition(|token| *token == session.refresh_token)
+            .map(|position| state.superseded.len() - position);
+        if rotations_since.is_none() {
+            if let Some((store, key)) = &self.session_store {
+                let superseded = store.superseded_tokens(key).await?;
+                rotations_since = superseded
+                    .iter()
+                    .rposition(|token| *token == session.refresh_token)
+                    .map(|position| superseded.len() - position);
+            }
+        }
+        if let Some(rotations_since) = rotations_since {
+            warn!(rotations_since, "superseded refresh token presented");
+            if let Some(hook) = &self.token_reuse_hook {
+                hook(TokenReuseEvent {
+                    session_key: self.session_store.as_ref().map(|(_, key)| key.clone()),
+                    rotations_since,
+                })
+                .await;
+            }
+            return Err(AuthError::RefreshTokenReused(None));
+        }
+
+        let superseded = state.rotate(session);
+        self.persist(&state.session, superseded.as_deref()).await
+    }
+
+    /// Returns an access token that is valid for at least the refresh margin
+    ///
+    /// The session is refreshed first if its access token is about to expire.
+    ///
+    /// # Errors
+    ///
+    /// Returns the refresh error if the session cannot be refreshed and no credential
+    /// provider is registered (or the provider fails as well).
+    pub async fn get_valid_token(&self) -> Result<String, AuthError> {
+        let mut state = self.state.lock().await;
+        if state.session.expires_within(self.refresh_margin) {
+            self.refresh_locked(&mut state).await?;
+        }
+        Ok(state.session.access_token.clone())
+    }
+
+    /// Refreshes the session regardless of its expiry
+    pub async fn refresh(&self) -> Result<Session, AuthError> {
+        let mut state = self.state.lock().await;
+        self.refresh_locked(&mut state).await?;
+        Ok(state.session.clone())
+    }
+
+    async fn refresh_locked(&self, state: &mut ManagedSession) -> Result<(), AuthError> {
+        let (session, reacquired) = self.refreshed(&state.session).await?;
+        let superseded = if reacquired {
+            // Credentials were re-acquired, so the session starts a new token family
+            state.session = session;
+            state.superseded.clear();
+            None
+        } else {
+            state.rotate(session)
+        };
+        self.persist(&state.session, superseded.as_deref()).await
+    }
+
+    async fn persist(&self, session: &Session, superseded: Option<&str>) -> Result<(), AuthError> {
+        let Some((store, key)) = &self.session_store else {
+            return Ok(());
+        };
+        if let Some(superseded) = superseded {
+            store.record_superseded(key, superseded).await?;
+        }
+        store.save(key, session).await
+    }
+
+    #[instrument(skip_all)]
+    async fn refreshed(&self, session: &Session) -> Result<(Session, bool), AuthError> {
+        let refresh_error = match self.client.refresh_token(&session.refresh_token).await {
+            Ok(token_response) => {
+                info!("session refreshed");
+                return Ok((Session::from(token_response), false));
+            }
+            Err(e) => e,
+        };
+
+        let permanent = matches!(
+            refresh_error,
+            AuthError::NotAuthorized(_)
+                | AuthError::InvalidParameters(_)
+                | AuthError::NotFound(_)
+                | AuthError::RefreshTokenReused(_)
+                | AuthError::RefreshTokenNotFound(_)
+        );
+        let Some(provider) = self.credential_provider.as_ref().filter(|_| permanent) else {
+            return Err(refresh_error);
+        };
+
+        warn!(%refresh_error, "refresh token rejected, re-acquiring credentials");
+        let session = provider.acquire(&self.client).await?;
+        info!("credentials re-acquired");
+        Ok((session, true))
+    }
+}
+
+impl AuthClient {
+    /// Creates a [`SessionManager`] keeping `session` valid with this client
+    ///
+    /// Accepts a [`Session`] or the `TokenResponse` of a sign-in, so callers can hand the
+    /// tokens over without converting them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let tokens = client
+    ///     .signin_with_password(
+    ///         IdType::Email("user@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///     )
+    ///     .await?;
+    /// let manager = client.session_manager(tokens);
+    ///
+    /// // Refreshed transparently once the access token is about to expire
+    /// let access_token = manager.get_valid_token().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn session_manager(&self, session: impl Into<Session>) -> SessionManager {
+        SessionManager::new(self.clone(), session.into())
+    }
+
+    /// Saves `session` to `store` under `key` and creates a [`SessionManager`] persisting
+    /// it there after every refresh
+    ///
+    /// Together with [`AuthClient::restore_session`] this keeps a user signed in across
+    /// process restarts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the session store's error if saving the session fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use supabase_auth_redux::{AuthClient, FileSessionStore, IdType};
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let store = Arc::new(FileSessionStore::new("/home/me/.config/my-cli/sessions"));
+    /// let tokens = client
+    ///     .signin_with_password(
+    ///         IdType::Email("user@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///     )
+    ///     .await?;
+    /// let manager = client.persist_session(tokens, store, "default").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn persist_session(
+        &self,
+        session: impl Into<Session>,
+        store: Arc<dyn SessionStore>,
+        key: &str,
+    ) -> Result<SessionManager, AuthError> {
+        let session = session.into();
+        store.save(key, &session).await?;
+        Ok(self.session_manager(session).with_session_store(store, key))
+    }
+
+    /// Creates a [`SessionManager`] for the session stored in `store` under `key`, if any
+    ///
+    /// The manager persists the session after every refresh. A session whose access token
+    /// expired while the process was not running is refreshed on the first
+    /// [`SessionManager::get_valid_token`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the session store's error if loading the session fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use supabase_auth_redux::{AuthClient, FileSessionStore};
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let store = Arc::new(FileSessionStore::new("/home/me/.config/my-cli/sessions"));
+    /// match client.restore_session(store, "default").await? {
+    ///     Some(manager) => println!("Signed in: {}", manager.get_valid_token().await?),
+    ///     None => println!("Run `my-cli login` first"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn restore_session(
+        &self,
+        store: Arc<dyn SessionStore>,
+        key: &str,
+    ) -> Result<Option<SessionManager>, AuthError> {
+        let Some(session) = store.load(key).await? else {
+            return Ok(None);
+        };
+        info!("session restored");
+        Ok(Some(
+            self.session_manager(session).with_session_store(store, key),
+        ))
+    }
+}
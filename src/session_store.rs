@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, error, info};
+
+use crate::error::AuthError;
+use crate::models::session::Session;
+use crate::session_manager::MAX_SUPERSEDED_TOKENS;
+
+/// How long a session may go unused before [`SessionStore::prune_expired`] removes it
+///
+/// Matches the 30 day refresh token inactivity timeout GoTrue projects commonly use.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Persistence for sessions, keyed by an application chosen identifier
+///
+/// Implement this trait to keep sessions in Redis or a database. [`MemorySessionStore`]
+/// keeps them in memory and [`FileSessionStore`] in files, so they survive restarts.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::{MemorySessionStore, Session, SessionStore};
+///
+/// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+/// let store = MemorySessionStore::new();
+/// store.save("user-42", &Session::default()).await?;
+///
+/// // Sessions unused for longer than the idle timeout are removed
+/// assert_eq!(store.prune_expired().await?, 1);
+/// assert_eq!(store.load("user-42").await?, None);
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Stores `session` under `key`, replacing any previous session
+    async fn save(&self, key: &str, session: &Session) -> Result<(), AuthError>;
+
+    /// Returns the session stored under `key`, if any
+    async fn load(&self, key: &str) -> Result<Option<Session>, AuthError>;
+
+    /// Removes the session stored under `key`, if any
+    async fn remove(&self, key: &str) -> Result<(), AuthError>;
+
+    /// Returns the keys of all stored sessions
+    async fn keys(&self) -> Result<Vec<String>, AuthError>;
+
+    /// Removes every stored session
+    ///
+    /// The default implementation removes the sessions of [`SessionStore::keys`] one by one.
+    async fn clear(&self) -> Result<(), AuthError> {
+        for key in self.keys().await? {
+            self.remove(&key).await?;
+        }
+        Ok(())
+    }
+
+    /// Records that `refresh_token` was rotated out of the session stored under `key`
+    ///
+    /// Used by [`SessionManager`](crate::SessionManager) to detect refresh token reuse
+    /// across processes. The default implementation does not record anything; stores should
+    /// keep a bounded number of recent tokens per key and drop them in
+    /// [`SessionStore::remove`].
+    async fn record_superseded(&self, key: &str, refresh_token: &str) -> Result<(), AuthError> {
+        let _ = (key, refresh_token);
+        Ok(())
+    }
+
+    /// Returns the refresh tokens recorded as rotated out of the session under `key`,
+    /// oldest first
+    async fn superseded_tokens(&self, key: &str) -> Result<Vec<String>, AuthError> {
+        let _ = key;
+        Ok(Vec::new())
+    }
+
+    /// How long a session may go unused before [`SessionStore::prune_expired`] removes it
+    ///
+    /// Defaults to [`DEFAULT_IDLE_TIMEOUT`].
+    fn idle_timeout(&self) -> Duration {
+        DEFAULT_IDLE_TIMEOUT
+    }
+
+    /// Removes every expired session and returns how many were removed
+    ///
+    /// A session expires once its access token has expired for longer than
+    /// [`SessionStore::idle_timeout`]; until then its refresh token can still renew it.
+    /// The default implementation loads each session through [`SessionStore::keys`] and
+    /// [`SessionStore::load`]; stores with native expiry (e.g. Redis TTLs) can override it
+    /// with something cheaper.
+    async fn prune_expired(&self) -> Result<usize, AuthError> {
+        let mut pruned = 0;
+        for key in self.keys().await? {
+            let Some(session) = self.load(&key).await? else {
+                continue;
+            };
+            if session.idle_for(self.idle_timeout()) {
+                debug!(key, "pruning expired session");
+                self.remove(&key).await?;
+                pruned += 1;
+            }
+        }
+        info!(pruned, "pruned expired sessions");
+        Ok(pruned)
+    }
+}
+
+/// Session store keeping sessions in memory
+#[derive(Debug)]
+pub struct MemorySessionStore {
+    /// Stored sessions by key
+    sessions: RwLock<HashMap<String, Session>>,
+    /// Refresh tokens rotated out of each session, oldest first
+    superseded: RwLock<HashMap<String, Vec<String>>>,
+    /// How long a session may go unused before it is pruned
+    idle_timeout: Duration,
+}
+
+impl Default for MemorySessionStore {
+    fn default() -> Self {
+        Self {
+            sessions: RwLock::default(),
+            superseded: RwLock::default(),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+}
+
+impl MemorySessionStore {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long a session may go unused before it is pruned
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    async fn save(&self, key: &str, session: &Session) -> Result<(), AuthError> {
+        self.sessions
+            .write()
+            .map_err(|_| AuthError::Internal)?
+            .insert(key.to_string(), session.clone());
+        Ok(())
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<Session>, AuthError> {
+        Ok(self
+            .sessions
+            .read()
+            .map_err(|_| AuthError::Internal)?
+            .get(key)
+            .cloned())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), AuthError> {
+        self.sessions
+            .write()
+            .map_err(|_| AuthError::Internal)?
+            .remove(key);
+        self.superseded
+            .write()
+            .map_err(|_| AuthError::Internal)?
+            .remove(key);
+        Ok(())
+    }
+
+    async fn record_superseded(&self, key: &str, refresh_token: &str) -> Result<(), AuthError> {
+        let mut superseded = self.superseded.write().map_err(|_| AuthError::Internal)?;
+        let tokens = superseded.entry(key.to_string()).or_default();
+        tokens.push(refresh_token.to_string());
+        if tokens.len() > MAX_SUPERSEDED_TOKENS {
+            tokens.remove(0);
+        }
+        Ok(())
+    }
+
+    async fn superseded_tokens(&self, key: &str) -> Result<Vec<String>, AuthError> {
+        Ok(self
+            .superseded
+            .read()
+            .map_err(|_| AuthError::Internal)?
+            .get(key)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, AuthError> {
+        Ok(self
+            .sessions
+            .read()
+            .map_err(|_| AuthError::Internal)?
+            .keys()
+            .cloned()
+            .collect())
+    }
+}
+
+/// Contents of a session file of a [`FileSessionStore`]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct StoredSession {
+    /// The stored session, absent if only superseded tokens were recorded
+    session: Option<Session>,
+    /// Refresh tokens rotated out of the session, oldest first
+    superseded: Vec<String>,
+}
+
+/// Session store keeping each session in a JSON file of a directory
+///
+/// Sessions survive process restarts, so CLI tools can remember the signed in user between
+/// runs. Files are only readable by the current user on Unix, since they hold refresh
+/// tokens.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use supabase_auth_redux::{FileSessionStore, SessionStore};
+///
+/// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+/// let store = FileSessionStore::new("/home/me/.config/my-cli/sessions");
+/// if let Some(session) = store.load("default").await? {
+///     println!("Signed in until {}", session.expires_at);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileSessionStore {
+    /// Directory holding the session files
+    dir: PathBuf,
+    /// Serializes read-modify-write cycles of the session files
+    lock: Mutex<()>,
+    /// How long a session may go unused before it is pruned
+    idle_timeout: Duration,
+}
+
+impl FileSessionStore {
+    /// Creates a store keeping sessions in `dir`, which is created on the first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+
+    /// Sets how long a session may go unused before it is pruned
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Returns the directory holding the session files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the file of `key`, whose name encodes the key so any key is a valid name
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", URL_SAFE_NO_PAD.encode(key)))
+    }
+
+    async fn read(&self, key: &str) -> Result<StoredSession, AuthError> {
+        let contents = match tokio::fs::read(self.path(key)).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(StoredSession::default()),
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        serde_json::from_slice(&contents).map_err(|e| {
+            error!("{}", e);
+            AuthError::Internal
+        })
+    }
+
+    /// Replaces the file of `key` through a temporary file, so readers never see a
+    /// partially written session
+    async fn write(&self, key: &str, stored: &StoredSession) -> Result<(), AuthError> {
+        let contents = serde_json::to_vec(stored).map_err(|e| {
+            error!("{}", e);
+            AuthError::Internal
+        })?;
+        let path = self.path(key);
+        let tmp_path = path.with_extension("json.tmp");
+        let result = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            let mut options = tokio::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+            let mut file = options.open(&tmp_path).await?;
+            tokio::io::AsyncWriteExt::write_all(&mut file, &contents).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&tmp_path, &path).await
+        }
+        .await;
+        result.map_err(|e| {
+            error!("{}", e);
+            AuthError::Internal
+        })
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    async fn save(&self, key: &str, session: &Session) -> Result<(), AuthError> {
+        let _guard = self.lock.lock().await;
+        let mut stored = self.read(key).await?;
+        stored.session = Some(session.clone());
+        self.write(key, &stored).await
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<Session>, AuthError> {
+        let _guard = self.lock.lock().await;
+        Ok(self.read(key).await?.session)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), AuthError> {
+        let _guard = self.lock.lock().await;
+        match tokio::fs::remove_file(self.path(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+
+    async fn record_superseded(&self, key: &str, refresh_token: &str) -> Result<(), AuthError> {
+        let _guard = self.lock.lock().await;
+        let mut stored = self.read(key).await?;
+        stored.superseded.push(refresh_token.to_string());
+        if stored.superseded.len() > MAX_SUPERSEDED_TOKENS {
+            stored.superseded.remove(0);
+        }
+        self.write(key, &stored).await
+    }
+
+    async fn superseded_tokens(&self, key: &str) -> Result<Vec<String>, AuthError> {
+        let _guard = self.lock.lock().await;
+        Ok(self.read(key).await?.superseded)
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, AuthError> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        let mut keys = Vec::new();
+        loop {
+            let entry = match entries.next_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            // Skips temporary files and files not written by the store
+            let key = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|name| URL_SAFE_NO_PAD.decode(name).ok())
+                .and_then(|key| String::from_utf8(key).ok());
+            keys.extend(key);
+        }
+        Ok(keys)
+    }
+}
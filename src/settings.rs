@@ -0,0 +1,109 @@
+use serde::de::DeserializeOwned;
+use tracing::{debug, error, instrument, trace_span, Instrument};
+
+use crate::models::settings::{HealthResponse, Settings};
+use crate::util::{error_with_body, handle_response};
+use crate::{AuthClient, AuthError};
+
+impl AuthClient {
+    /// Checks that the auth server is up
+    ///
+    /// Suitable for readiness probes of services depending on the auth server.
+    ///
+    /// # Returns
+    ///
+    /// Returns the server's name and version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Http` if the server cannot be reached.
+    /// Returns `AuthError::GeneralError` if the server reports an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let health = client.health().await?;
+    /// println!("{} {}", health.name, health.version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn health(&self) -> Result<HealthResponse, AuthError> {
+        self.observe("health", None, async {
+            self.get_public("health")
+                .instrument(trace_span!("gotrue health"))
+                .await
+        })
+        .await
+    }
+
+    /// Fetches the public settings of the auth server
+    ///
+    /// Describes which providers are enabled and whether signups and confirmations are
+    /// required, e.g. to only show the sign-in options a project supports.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let settings = client.settings().await?;
+    /// for provider in settings.enabled_providers() {
+    ///     println!("Sign in with {}", provider);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn settings(&self) -> Result<Settings, AuthError> {
+        self.observe("settings", None, async {
+            self.get_public("settings")
+                .instrument(trace_span!("gotrue settings"))
+                .await
+        })
+        .await
+    }
+
+    /// Fetches a public endpoint that only requires the anonymous key
+    async fn get_public<T: DeserializeOwned>(&self, path: &str) -> Result<T, AuthError> {
+        let resp = match self
+            .send_with_retry(|api_url| {
+                self.http_client
+                    .get(format!("{}/{}", api_url, path))
+                    .header("apiKey", &self.supabase_anon_key)
+            })
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+
+        let resp_code_result = handle_response(&resp).await;
+        let resp_text = match resp.text().await {
+            Ok(resp_text) => resp_text,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        debug!("resp_text: {}", self.redaction.response(&resp_text));
+        resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+        match serde_json::from_str::<T>(&resp_text) {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+}
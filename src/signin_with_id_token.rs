@@ -0,0 +1,128 @@
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::provider::Provider;
+use crate::models::token::TokenResponse;
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+#[derive(Debug, Serialize)]
+struct TokenIdTokenGrant<'a> {
+    provider: &'a str,
+    id_token: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_token: Option<&'a str>,
+}
+
+impl AuthClient {
+    /// Signs in a user with an OpenID Connect ID token issued by a provider
+    ///
+    /// Native apps obtain the ID token from the platform's sign-in SDK (e.g. Sign in with
+    /// Apple or Google Sign-In) and exchange it for a session without a browser redirect.
+    /// The provider must be enabled in the project, with the app's client ID allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider that issued the ID token
+    /// * `id_token` - The ID token
+    /// * `nonce` - The raw nonce, if a hashed nonce was passed to the provider's SDK
+    /// * `access_token` - The provider access token, if the ID token carries an `at_hash`
+    ///   claim
+    ///
+    /// # Returns
+    ///
+    /// Returns a `TokenResponse` containing access and refresh tokens on successful
+    /// authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the ID token is empty, the provider does
+    /// not issue ID tokens accepted by GoTrue, or the server rejects the token.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::provider::Provider;
+    /// # async fn example(client: AuthClient, id_token: &str, raw_nonce: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let tokens = client
+    ///     .signin_with_id_token(Provider::Apple, id_token, Some(raw_nonce), None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signin_with_id_token(
+        &self,
+        provider: Provider,
+        id_token: &str,
+        nonce: Option<&str>,
+        access_token: Option<&str>,
+    ) -> Result<TokenResponse, AuthError> {
+        self.observe("signin_with_id_token", None, async {
+            if !provider.supports_id_token() {
+                error!(
+                    provider = provider.as_str(),
+                    "provider does not issue id tokens"
+                );
+                return Err(AuthError::InvalidParameters(None));
+            }
+            if id_token.is_empty() {
+                error!("empty id_token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let grant = TokenIdTokenGrant {
+                provider: provider.as_str(),
+                id_token,
+                nonce,
+                access_token,
+            };
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .post(format!("{}/token?grant_type=id_token", api_url))
+                        .bearer_auth(&self.supabase_anon_key)
+                        .header("apiKey", &self.supabase_anon_key)
+                        .json(&grant)
+                })
+                .instrument(trace_span!("gotrue token id_token"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+                Ok(token_response) => token_response,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(provider = provider.as_str(), "signed in with id token");
+
+            Ok(token_response)
+        })
+        .await
+    }
+}
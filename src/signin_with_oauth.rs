@@ -0,0 +1,238 @@
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use url::Url;
+
+use crate::error::AuthError;
+use crate::models::oauth::{OAuthAuthorizeUrl, OAuthOptions};
+use crate::models::provider::Provider;
+use crate::models::session::Session;
+use crate::models::token::TokenResponse;
+use crate::pkce::{generate_code_verifier, CodeChallenge};
+use crate::util::{error_with_body, handle_response};
+use crate::AuthClient;
+
+#[derive(Debug, Serialize)]
+struct TokenPkceGrant<'a> {
+    auth_code: &'a str,
+    code_verifier: &'a str,
+}
+
+impl AuthClient {
+    /// Builds the URL starting an OAuth sign-in with a third-party provider
+    ///
+    /// The sign-in uses the PKCE flow: redirect the user's browser to the returned URL and
+    /// keep the returned code verifier. After the provider authenticates the user, GoTrue
+    /// redirects to `redirect_to` with a `code` query parameter, which
+    /// [`AuthClient::exchange_code_for_session`] exchanges together with the verifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider to sign in with
+    /// * `redirect_to` - URL to return to after sign-in; must be in the project's allow list.
+    ///   The client's default redirect URL, or else the project's site URL, is used when `None`.
+    /// * `scopes` - Additional provider scopes to request
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the provider is `email`, `phone` or
+    /// `anonymous`, which do not sign in through OAuth.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::provider::Provider;
+    ///
+    /// # fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let authorize = client.get_oauth_authorize_url(
+    ///     Provider::Github,
+    ///     Some("https://app.example.com/auth/callback"),
+    ///     &["read:org"],
+    /// )?;
+    /// // Store `authorize.code_verifier`, then redirect the browser to `authorize.url`
+    /// assert!(authorize.url.contains("provider=github"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self), fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context))]
+    pub fn get_oauth_authorize_url(
+        &self,
+        provider: Provider,
+        redirect_to: Option<&str>,
+        scopes: &[&str],
+    ) -> Result<OAuthAuthorizeUrl, AuthError> {
+        let mut options = OAuthOptions::new();
+        options.redirect_to = redirect_to.map(str::to_string);
+        options.scopes = scopes.iter().map(|scope| scope.to_string()).collect();
+        self.get_oauth_authorize_url_with_options(provider, options)
+    }
+
+    /// Builds the URL starting an OAuth sign-in with additional options
+    ///
+    /// Behaves like [`AuthClient::get_oauth_authorize_url`], additionally passing provider
+    /// specific query parameters, e.g. Google's `access_type=offline` and `prompt=consent`
+    /// to receive a provider refresh token, or `prompt=select_account` to force the account
+    /// selection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the provider is `email`, `phone` or
+    /// `anonymous`, which do not sign in through OAuth.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::oauth::OAuthOptions;
+    /// use supabase_auth_redux::models::provider::Provider;
+    ///
+    /// # fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let authorize = client.get_oauth_authorize_url_with_options(
+    ///     Provider::Google,
+    ///     OAuthOptions::new()
+    ///         .scope("https://www.googleapis.com/auth/calendar.readonly")
+    ///         .query_param("access_type", "offline")
+    ///         .query_param("prompt", "consent"),
+    /// )?;
+    /// assert!(authorize.url.contains("access_type=offline"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self), fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context))]
+    pub fn get_oauth_authorize_url_with_options(
+        &self,
+        provider: Provider,
+        options: OAuthOptions,
+    ) -> Result<OAuthAuthorizeUrl, AuthError> {
+        if matches!(
+            provider,
+            Provider::Email | Provider::Phone | Provider::Anonymous
+        ) {
+            error!(%provider, "provider does not support oauth");
+            return Err(AuthError::InvalidParameters(None));
+        }
+
+        let mut url = match Url::parse(&format!("{}/authorize", self.api_urls.primary())) {
+            Ok(url) => url,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::InvalidParameters(None));
+            }
+        };
+
+        let code_verifier = generate_code_verifier();
+        let challenge = CodeChallenge::from_verifier(&code_verifier);
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("provider", provider.as_str());
+            if let Some(redirect_to) = self.redirect_url(options.redirect_to.as_deref()) {
+                query.append_pair("redirect_to", redirect_to);
+            }
+            if !options.scopes.is_empty() {
+                query.append_pair("scopes", &options.scopes.join(" "));
+            }
+            if options.skip_http_redirect {
+                query.append_pair("skip_http_redirect", "true");
+            }
+            for (key, value) in &options.query_params {
+                query.append_pair(key, value);
+            }
+            query.append_pair("code_challenge", &challenge.code_challenge);
+            query.append_pair("code_challenge_method", &challenge.code_challenge_method);
+        }
+
+        Ok(OAuthAuthorizeUrl {
+            url: url.into(),
+            code_verifier,
+        })
+    }
+
+    /// Exchanges the authorization code of a PKCE redirect for a session
+    ///
+    /// # Arguments
+    ///
+    /// * `auth_code` - The `code` query parameter of the redirect, see
+    ///   [`parse_session_from_url`](crate::parse_session_from_url)
+    /// * `code_verifier` - The code verifier created with the authorization URL
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the code or verifier is empty, or the
+    /// code was already used.
+    /// Returns `AuthError::NotAuthorized` if the code expired or the verifier does not match.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{parse_session_from_url, AuthClient, UrlSession};
+    /// # async fn example(client: AuthClient, callback_url: &str, code_verifier: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// if let UrlSession::Code(code) = parse_session_from_url(callback_url)? {
+    ///     let session = client.exchange_code_for_session(&code, code_verifier).await?;
+    ///     println!("Signed in until {}", session.expires_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn exchange_code_for_session(
+        &self,
+        auth_code: &str,
+        code_verifier: &str,
+    ) -> Result<Session, AuthError> {
+        self.observe("exchange_code_for_session", None, async {
+            if auth_code.is_empty() || code_verifier.is_empty() {
+                error!("empty auth code or code verifier");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let grant = TokenPkceGrant {
+                auth_code,
+                code_verifier,
+            };
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    self.http_client
+                        .post(format!("{}/token?grant_type=pkce", api_url))
+                        .bearer_auth(&self.supabase_anon_key)
+                        .header("apiKey", &self.supabase_anon_key)
+                        .json(&grant)
+                })
+                .instrument(trace_span!("gotrue token pkce"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+                Ok(token_response) => token_response,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!("exchanged auth code");
+
+            Ok(Session::from(token_response))
+        })
+        .await
+    }
+}
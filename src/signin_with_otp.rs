@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::otp::{OtpChannel, OtpOptions};
+use crate::pkce::CodeChallenge;
+use crate::util::{error_with_body, handle_response, MetaSecurity};
+use crate::{AuthClient, IdType};
+
+/// GoTrue error code returned when OTP sign-in would have to create a user
+const OTP_SIGNUPS_DISABLED: &str = "otp_disabled";
+
+#[derive(Debug, Serialize)]
+struct OtpRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phone: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<OtpChannel>,
+    create_user: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<&'a HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gotrue_meta_security: Option<MetaSecurity<'a>>,
+    #[serde(flatten)]
+    code_challenge: Option<&'a CodeChallenge>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default)]
+struct OtpErrorBody {
+    error_code: Option<String>,
+    msg: Option<String>,
+}
+
+impl AuthClient {
+    /// Sends a one-time code or magic link to an email address or phone number
+    ///
+    /// Emails contain a magic link (and the code, depending on the email template); phone
+    /// numbers receive the code over the configured [`OtpChannel`].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The email address or phone number to send the code to
+    /// * `options` - Whether to create unknown users, redirect URL, metadata and channel
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the email or phone number is empty or
+    /// rejected by the server.
+    /// Returns `AuthError::UserNotFound` if `should_create_user` is `false` and no user
+    /// exists for the email or phone number.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, AuthError, IdType};
+    /// # use supabase_auth_redux::models::otp::OtpOptions;
+    /// # async fn example() -> Result<(), AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// match client
+    ///     .signin_with_otp(
+    ///         IdType::Email("user@example.com".to_string()),
+    ///         OtpOptions::new().should_create_user(false),
+    ///     )
+    ///     .await
+    /// {
+    ///     Ok(()) => println!("Check your inbox"),
+    ///     Err(AuthError::UserNotFound) => println!("No account for this address"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signin_with_otp(&self, id: IdType, options: OtpOptions) -> Result<(), AuthError> {
+        self.observe("signin_with_otp", Some(id.to_string()), async {
+            let id = self.normalize_id(id);
+            let (email, phone) = match &id {
+                IdType::Email(email) => (Some(email.as_str()), None),
+                IdType::PhoneNumber(phone_number) => (None, Some(phone_number.as_str())),
+            };
+            if email.or(phone).is_none_or(str::is_empty) {
+                error!("empty email or phone_number");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let body = OtpRequest {
+                email,
+                phone,
+                channel: phone.map(|_| options.channel),
+                create_user: options.should_create_user,
+                data: options.data.as_ref(),
+                gotrue_meta_security: MetaSecurity::from_token(options.captcha_token.as_deref()),
+                code_challenge: options.code_challenge.as_ref(),
+            };
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    let mut request = self
+                        .http_client
+                        .post(format!("{}/otp", api_url))
+                        .bearer_auth(&self.supabase_anon_key)
+                        .header("apiKey", &self.supabase_anon_key)
+                        .json(&body);
+                    if let Some(redirect_to) =
+                        self.redirect_url(options.email_redirect_to.as_deref())
+                    {
+                        request = request.query(&[("redirect_to", redirect_to)]);
+                    }
+                    request
+                })
+                .instrument(trace_span!("gotrue otp"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+
+            if resp_code_result.is_err() && !options.should_create_user {
+                let error_body: OtpErrorBody = serde_json::from_str(&resp_text).unwrap_or_default();
+                // Older GoTrue versions only report the condition in the message
+                let signups_disabled = error_body.error_code.as_deref()
+                    == Some(OTP_SIGNUPS_DISABLED)
+                    || error_body
+                        .msg
+                        .is_some_and(|msg| msg.contains("Signups not allowed"));
+                if signups_disabled {
+                    info!("no user for otp sign-in");
+                    return Err(AuthError::UserNotFound);
+                }
+            }
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            info!("otp sent");
+            Ok(())
+        })
+        .await
+    }
+}
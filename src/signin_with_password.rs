@@ -1,17 +1,22 @@
-use serde::{Deserialize, Serialize};
-use tracing::{debug, error, info, instrument, trace_span, Instrument};
+use reqwest::Method;
+use serde::Serialize;
+use tracing::{debug, error, info, instrument};
 
 use crate::error::AuthError;
+use crate::models::password::SigninOptions;
+use crate::models::raw::Raw;
 use crate::models::token::TokenResponse;
-use crate::util::handle_response_code;
+use crate::util::{parse_with_raw, Credentials, Endpoint, MetaSecurity};
 use crate::AuthClient;
 use crate::IdType;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct TokenPasswordGrant {
+#[derive(Debug, Serialize)]
+struct TokenPasswordGrant<'a> {
     email: Option<String>,
     phone: Option<String>,
     password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gotrue_meta_security: Option<MetaSecurity<'a>>,
 }
 
 impl AuthClient {
@@ -53,92 +58,169 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip_all)]
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn signin_with_password(
         &self,
         id: IdType,
         password: String,
     ) -> Result<TokenResponse, AuthError> {
+        self.observe("signin_with_password", Some(id.to_string()), async {
+            self.signin_with_password_request(id, password, None).await
+        })
+        .await
+    }
+
+    /// Signs in a user with their email/phone and password and additional options
+    ///
+    /// Behaves like [`AuthClient::signin_with_password`], with the captcha token taken
+    /// from `options`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// use supabase_auth_redux::models::password::SigninOptions;
+    ///
+    /// # async fn example(client: AuthClient, captcha_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let tokens = client
+    ///     .signin_with_password_options(
+    ///         IdType::Email("user@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///         SigninOptions::new().captcha_token(captcha_token),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signin_with_password_options(
+        &self,
+        id: IdType,
+        password: String,
+        options: SigninOptions,
+    ) -> Result<TokenResponse, AuthError> {
+        self.observe("signin_with_password", Some(id.to_string()), async {
+            self.signin_with_password_request(id, password, options.captcha_token.as_deref())
+                .await
+        })
+        .await
+    }
+
+    /// Signs in a user with their email/phone and password, keeping the raw response
+    ///
+    /// Behaves like [`AuthClient::signin_with_password`], but also returns the raw JSON
+    /// response body so fields not modelled by `TokenResponse` remain accessible.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let tokens = client
+    ///     .signin_with_password_with_raw(
+    ///         IdType::Email("user@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("Access token: {}", tokens.value.access_token);
+    /// println!("Raw response: {}", tokens.raw);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signin_with_password_with_raw(
+        &self,
+        id: IdType,
+        password: String,
+    ) -> Result<Raw<TokenResponse>, AuthError> {
+        self.observe("signin_with_password", Some(id.to_string()), async {
+            let resp_text = self.signin_with_password_text(id, password, None).await?;
+            parse_with_raw(&resp_text)
+        })
+        .await
+    }
+
+    /// Sends a password sign-in request; shared by [`AuthClient::signin_with_password`] and
+    /// the sign-in builder
+    pub(crate) async fn signin_with_password_request(
+        &self,
+        id: IdType,
+        password: String,
+        captcha_token: Option<&str>,
+    ) -> Result<TokenResponse, AuthError> {
+        let resp_text = self
+            .signin_with_password_text(id, password, captcha_token)
+            .await?;
+
+        let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
+            Ok(token_response) => token_response,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Internal);
+            }
+        };
+        info!(
+            tokens_are_nonempty =
+                !token_response.access_token.is_empty() && !token_response.refresh_token.is_empty()
+        );
+        debug!(
+            token = self.redaction.token(&token_response.access_token),
+            refresh_token = self.redaction.token(&token_response.refresh_token)
+        );
+
+        Ok(token_response)
+    }
+
+    async fn signin_with_password_text(
+        &self,
+        id: IdType,
+        password: String,
+        captcha_token: Option<&str>,
+    ) -> Result<String, AuthError> {
         if password.is_empty() {
             error!("empty password");
-            return Err(AuthError::InvalidParameters);
+            return Err(AuthError::InvalidParameters(None));
         }
 
-        let token_password_grant = match id {
+        let token_password_grant = match self.normalize_id(id) {
             IdType::Email(email) => {
                 if email.is_empty() {
                     error!("empty email");
-                    return Err(AuthError::InvalidParameters);
+                    return Err(AuthError::InvalidParameters(None));
                 }
 
-                info!(email = email);
+                info!(email = self.redaction.identifier(&email));
                 TokenPasswordGrant {
                     email: Some(email),
                     phone: None,
                     password,
+                    gotrue_meta_security: MetaSecurity::from_token(captcha_token),
                 }
             }
             IdType::PhoneNumber(phone_number) => {
                 if phone_number.is_empty() {
                     error!("empty phone_number");
-                    return Err(AuthError::InvalidParameters);
+                    return Err(AuthError::InvalidParameters(None));
                 }
 
-                info!(phone_number = phone_number);
+                info!(phone_number = self.redaction.identifier(&phone_number));
                 TokenPasswordGrant {
                     email: None,
                     phone: Some(phone_number),
                     password,
+                    gotrue_meta_security: MetaSecurity::from_token(captcha_token),
                 }
             }
         };
 
-        let resp = match self
-            .http_client
-            .post(format!(
-                "{}/auth/v1/{}",
-                self.supabase_api_url, "token?grant_type=password"
-            ))
-            .bearer_auth(&self.supabase_anon_key)
-            .header("apiKey", &self.supabase_anon_key)
-            .json(&token_password_grant)
-            .send()
-            .instrument(trace_span!("gotrue token password"))
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                log::error!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result?;
-
-        let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
-            Ok(token_response) => token_response,
-            Err(e) => {
-                error!("{}", e);
-                return Err(AuthError::Internal);
-            }
-        };
-        info!(
-            tokens_are_nonempty =
-                !token_response.access_token.is_empty() && !token_response.refresh_token.is_empty()
-        );
-        debug!(
-            token = token_response.access_token,
-            refresh_token = token_response.refresh_token
-        );
-
-        Ok(token_response)
+        self.execute_text(
+            Endpoint::new(Method::POST, "token", Credentials::Anon)
+                .query("grant_type", "password")
+                .json(&token_password_grant),
+        )
+        .await
     }
 }
@@ -1,29 +1,30 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
-use tracing::{debug, info, trace_span, Instrument};
+use reqwest::Method;
+use serde::Serialize;
+use tracing::{debug, info, instrument};
 
 use crate::error::AuthError;
+use crate::models::otp::OtpChannel;
+use crate::models::password::{SignupOptions, SignupResult};
+use crate::models::token::TokenResponse;
 use crate::models::user::UserSchema;
-use crate::util::handle_response_code;
+use crate::pkce::CodeChallenge;
+use crate::util::{Credentials, Endpoint, MetaSecurity};
 use crate::{AuthClient, IdType};
 
-#[derive(Debug, Serialize, Deserialize)]
-struct SignupRequest {
+#[derive(Debug, Serialize)]
+struct SignupRequest<'a> {
     pub email: Option<String>,
     pub phone_number: Option<String>,
     pub password: String,
-    pub data: Option<HashMap<String, String>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct SignupResponse {
-    pub access_token: String,
-    pub token_type: String,
-    pub expires_in: i64,
-    pub expires_at: i64,
-    pub refresh_token: String,
-    pub user: UserSchema,
+    pub data: Option<&'a HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<OtpChannel>,
+    #[serde(flatten)]
+    pub code_challenge: Option<&'a CodeChallenge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gotrue_meta_security: Option<MetaSecurity<'a>>,
 }
 
 impl AuthClient {
@@ -37,7 +38,8 @@ impl AuthClient {
     ///
     /// * `signup_id_type` - The user's identifier (email or phone number)
     /// * `password` - The desired password for the account
-    /// * `metadata` - Optional user metadata to store with the account
+    /// * `metadata` - Optional user metadata to store with the account; values can be any
+    ///   JSON, including nested objects
     ///
     /// # Returns
     ///
@@ -45,22 +47,32 @@ impl AuthClient {
     /// - The newly created `UserSchema` with user information
     /// - An access token string for immediate authentication
     ///
+    /// Use [`AuthClient::signup_with_session`] to keep the refresh token as well, or on
+    /// projects requiring the user to confirm the email address or phone number first.
+    ///
     /// # Errors
     ///
     /// Returns `AuthError::InvalidParameters` if required fields are missing.
-    /// Returns `AuthError::Http` if the API request fails or user already exists.
+    /// Returns `AuthError::WeakPassword` if the password violates the configured password policy
+    /// or the project's password requirements.
+    /// Returns `AuthError::UserAlreadyExists` if the email or phone number is already
+    /// registered. Projects requiring email confirmation hide existing users instead.
+    /// Returns `AuthError::ConfirmationRequired` if the user was created but has to confirm
+    /// the email address or phone number before signing in.
+    /// Returns `AuthError::Http` if the API request fails.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use supabase_auth_redux::{AuthClient, IdType};
     /// # use std::collections::HashMap;
+    /// # use serde_json::json;
     /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
     /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
     ///
     /// let mut metadata = HashMap::new();
-    /// metadata.insert("first_name".to_string(), "John".to_string());
-    /// metadata.insert("last_name".to_string(), "Doe".to_string());
+    /// metadata.insert("first_name".to_string(), json!("John"));
+    /// metadata.insert("settings".to_string(), json!({"theme": "dark", "newsletter": true}));
     ///
     /// let (user, access_token) = client
     ///     .signup(
@@ -74,56 +86,158 @@ impl AuthClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
     pub async fn signup(
         &self,
         signup_id_type: IdType,
         password: String,
-        _metadata: Option<HashMap<String, String>>,
+        metadata: Option<HashMap<String, serde_json::Value>>,
     ) -> Result<(UserSchema, String), AuthError> {
-        let body = match signup_id_type {
+        self.observe("signup", Some(signup_id_type.to_string()), async {
+            let options = SignupOptions {
+                data: metadata,
+                ..SignupOptions::default()
+            };
+            self.signup_request(signup_id_type, password, &options)
+                .await
+                .and_then(user_and_access_token)
+        })
+        .await
+    }
+
+    /// Creates a new user account with additional options
+    ///
+    /// Behaves like [`AuthClient::signup`], with the metadata, confirmation redirect,
+    /// phone channel, captcha token and PKCE code challenge taken from `options`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// use supabase_auth_redux::models::password::SignupOptions;
+    ///
+    /// # async fn example(client: AuthClient, captcha_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let (user, access_token) = client
+    ///     .signup_with_options(
+    ///         IdType::Email("newuser@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///         SignupOptions::new()
+    ///             .metadata("first_name", "John")
+    ///             .redirect_to("https://app.example.com/welcome")
+    ///             .captcha_token(captcha_token),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signup_with_options(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        options: SignupOptions,
+    ) -> Result<(UserSchema, String), AuthError> {
+        self.observe("signup", Some(signup_id_type.to_string()), async {
+            self.signup_request(signup_id_type, password, &options)
+                .await
+                .and_then(user_and_access_token)
+        })
+        .await
+    }
+
+    /// Creates a new user account and returns the complete session
+    ///
+    /// Behaves like [`AuthClient::signup_with_options`], but returns the whole
+    /// `TokenResponse` of the new user, including the refresh token and expiry, so the
+    /// session can be kept without signing in again. Projects requiring email or phone
+    /// confirmation return [`SignupResult::ConfirmationRequired`] with the unconfirmed user
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType, Session};
+    /// use supabase_auth_redux::models::password::{SignupOptions, SignupResult};
+    ///
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// match client
+    ///     .signup_with_session(
+    ///         IdType::Email("newuser@example.com".to_string()),
+    ///         "secure_password".to_string(),
+    ///         SignupOptions::new(),
+    ///     )
+    ///     .await?
+    /// {
+    ///     SignupResult::Session(tokens) => {
+    ///         let session = Session::from(tokens);
+    ///     }
+    ///     SignupResult::ConfirmationRequired(user) => {
+    ///         println!("confirmation sent to {:?}", user.email);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn signup_with_session(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        options: SignupOptions,
+    ) -> Result<SignupResult, AuthError> {
+        self.observe("signup", Some(signup_id_type.to_string()), async {
+            self.signup_request(signup_id_type, password, &options)
+                .await
+        })
+        .await
+    }
+
+    /// Sends a signup request; shared by the signup methods and the signup builder
+    pub(crate) async fn signup_request(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        options: &SignupOptions,
+    ) -> Result<SignupResult, AuthError> {
+        self.check_password_policy(&password)?;
+
+        let data = options.data.as_ref();
+        let code_challenge = options.code_challenge.as_ref();
+        let captcha_token = options.captcha_token.as_deref();
+
+        let body = match self.normalize_id(signup_id_type) {
             IdType::Email(email) => SignupRequest {
                 email: Some(email),
                 phone_number: None,
                 password,
-                data: _metadata,
+                data,
+                channel: None,
+                code_challenge,
+                gotrue_meta_security: MetaSecurity::from_token(captcha_token),
             },
             IdType::PhoneNumber(phone_number) => SignupRequest {
                 email: None,
                 phone_number: Some(phone_number),
                 password,
-                data: _metadata,
+                data,
+                channel: options.channel,
+                code_challenge,
+                gotrue_meta_security: MetaSecurity::from_token(captcha_token),
             },
         };
 
-        let resp = match self
-            .http_client
-            .post(format!("{}/auth/v1/{}", self.supabase_api_url, "signup"))
-            .header("apiKey", &self.supabase_anon_key)
-            .bearer_auth(&self.supabase_anon_key)
-            .json(&body)
-            .send()
-            .instrument(trace_span!("gotrue create user"))
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                debug!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
+        let resp_text = self
+            .execute_text(
+                Endpoint::new(Method::POST, "signup", Credentials::Anon)
+                    .query_opt(
+                        "redirect_to",
+                        self.redirect_url(options.redirect_to.as_deref()),
+                    )
+                    .json(&body),
+            )
+            .await?;
 
-        let resp_code_result = handle_response_code(resp.status()).await;
-        let resp_text = match resp.text().await {
-            Ok(resp_text) => resp_text,
-            Err(e) => {
-                debug!("{}", e);
-                return Err(AuthError::Http);
-            }
-        };
-        debug!("resp_text: {}", resp_text);
-        resp_code_result?;
-
-        let created_user_resp = match serde_json::from_str::<SignupResponse>(&resp_text) {
+        let token_response = match serde_json::from_str::<TokenResponse>(&resp_text) {
             Ok(token_response) => token_response,
             Err(e) => {
                 debug!("{}", e);
@@ -131,9 +245,56 @@ impl AuthClient {
             }
         };
 
-        let created_user = created_user_resp.user;
+        // Without a session GoTrue responds with the bare user awaiting confirmation
+        if token_response.access_token.is_empty() {
+            let created_user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                Ok(user) if !user.id.is_nil() => user,
+                Ok(_) => {
+                    debug!("signup response without session or user");
+                    return Err(AuthError::Internal);
+                }
+                Err(e) => {
+                    debug!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(
+                user_id = created_user.id.to_string(),
+                "created user awaiting confirmation"
+            );
+            return Ok(SignupResult::ConfirmationRequired(created_user));
+        }
+
+        let Some(created_user) = token_response.user.as_ref() else {
+            debug!("signup response without user");
+            return Err(AuthError::Internal);
+        };
         info!(user_id = created_user.id.to_string(), "created user");
 
-        Ok((created_user, created_user_resp.access_token))
+        Ok(SignupResult::Session(token_response))
+    }
+}
+
+/// Splits a signup result into the user and access token returned by [`AuthClient::signup`]
+///
+/// Users awaiting confirmation have no access token, which is reported as
+/// [`AuthError::ConfirmationRequired`].
+pub(crate) fn user_and_access_token(
+    result: SignupResult,
+) -> Result<(UserSchema, String), AuthError> {
+    match result {
+        SignupResult::Session(TokenResponse {
+            user: Some(user),
+            access_token,
+            ..
+        }) => Ok((user, access_token)),
+        SignupResult::Session(_) => Err(AuthError::Internal),
+        SignupResult::ConfirmationRequired(user) => {
+            info!(
+                user_id = user.id.to_string(),
+                "signup requires confirmation before a session is issued"
+            );
+            Err(AuthError::ConfirmationRequired)
+        }
     }
 }
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::models::token::TokenResponse;
+use crate::models::user::UserSchema;
+use crate::{AuthClient, AuthError, IdType};
+
+/// Core auth operations, implemented by [`AuthClient`]
+///
+/// Services taking `impl SupabaseAuth` (or `Arc<dyn SupabaseAuth>`) instead of the concrete
+/// client can be tested with a fake and swap implementations without further changes.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::{AuthError, SupabaseAuth};
+///
+/// async fn email_of(auth: &dyn SupabaseAuth, token: &str) -> Result<String, AuthError> {
+///     let user = auth.get_user_by_token(token).await?;
+///     Ok(user.email.unwrap_or_default())
+/// }
+/// ```
+#[async_trait]
+pub trait SupabaseAuth: Send + Sync {
+    /// Creates a new user, see [`AuthClient::signup`]
+    async fn signup(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(UserSchema, String), AuthError>;
+
+    /// Signs in with an email address or phone number and a password, see
+    /// [`AuthClient::signin_with_password`]
+    async fn signin_with_password(
+        &self,
+        id: IdType,
+        password: String,
+    ) -> Result<TokenResponse, AuthError>;
+
+    /// Exchanges a refresh token for new tokens, see [`AuthClient::refresh_token`]
+    async fn refresh_token(&self, token: &str) -> Result<TokenResponse, AuthError>;
+
+    /// Returns the user an access token belongs to, see [`AuthClient::get_user_by_token`]
+    async fn get_user_by_token(&self, auth_token: &str) -> Result<UserSchema, AuthError>;
+
+    /// Ends the session of an access token, see [`AuthClient::logout`]
+    async fn logout(&self, token: &str) -> Result<(), AuthError>;
+
+    /// Permanently deletes a user, see [`AuthClient::hard_delete_user`]
+    async fn hard_delete_user(&self, user_id: Uuid) -> Result<(), AuthError>;
+}
+
+#[async_trait]
+impl SupabaseAuth for AuthClient {
+    async fn signup(
+        &self,
+        signup_id_type: IdType,
+        password: String,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(UserSchema, String), AuthError> {
+        AuthClient::signup(self, signup_id_type, password, metadata).await
+    }
+
+    async fn signin_with_password(
+        &self,
+        id: IdType,
+        password: String,
+    ) -> Result<TokenResponse, AuthError> {
+        AuthClient::signin_with_password(self, id, password).await
+    }
+
+    async fn refresh_token(&self, token: &str) -> Result<TokenResponse, AuthError> {
+        AuthClient::refresh_token(self, token).await
+    }
+
+    async fn get_user_by_token(&self, auth_token: &str) -> Result<UserSchema, AuthError> {
+        AuthClient::get_user_by_token(self, auth_token).await
+    }
+
+    async fn logout(&self, token: &str) -> Result<(), AuthError> {
+        AuthClient::logout(self, token).await
+    }
+
+    async fn hard_delete_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        AuthClient::hard_delete_user(self, user_id).await
+    }
+}
@@ -0,0 +1,459 @@
+//! Helpers for end-to-end tests against a local Supabase stack
+//!
+//! Enabled with the `test-util` feature. [`MailCapture`] reads the emails caught by the
+//! Mailpit or Inbucket instance started by `supabase start` (port 54324 by default), so tests
+//! can follow confirmation, magic link and recovery emails without a real inbox, and
+//! [`Totp`] generates the codes of an enrolled TOTP factor for MFA flows.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use supabase_auth_redux::test_util::MailCapture;
+//! use supabase_auth_redux::PollOptions;
+//!
+//! # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+//! let mail = MailCapture::mailpit("http://127.0.0.1:54324");
+//! let email = mail
+//!     .wait_for_email("test@example.com", PollOptions::default())
+//!     .await?;
+//!
+//! let token_hash = email.token_hash().expect("confirmation email carries a token");
+//! # Ok(())
+//! # }
+//! ```
+
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha1::Sha1;
+use tracing::{debug, error, instrument};
+use url::Url;
+
+use crate::error::AuthError;
+use crate::wait_for_confirmation::PollOptions;
+
+/// Mail catcher backing a [`MailCapture`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    /// Mailpit, used by current versions of the Supabase CLI
+    Mailpit,
+    /// Inbucket, used by older versions of the Supabase CLI
+    Inbucket,
+}
+
+/// Client for the mail catcher of a local Supabase stack
+#[derive(Debug, Clone)]
+pub struct MailCapture {
+    /// HTTP client for the mail catcher API
+    http_client: reqwest::Client,
+    /// Base URL of the mail catcher (e.g. `http://127.0.0.1:54324`)
+    base_url: String,
+    /// Which API the mail catcher speaks
+    backend: Backend,
+}
+
+/// Email caught by the mail catcher
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedEmail {
+    /// Subject line
+    pub subject: String,
+    /// Plain text body
+    pub text: String,
+    /// HTML body
+    pub html: String,
+}
+
+impl CapturedEmail {
+    /// Returns the first GoTrue verification link in the email
+    ///
+    /// This is the `{{ .ConfirmationURL }}` of the email templates, pointing at
+    /// `/auth/v1/verify`.
+    pub fn action_link(&self) -> Option<String> {
+        [&self.html, &self.text]
+            .into_iter()
+            .flat_map(|body| find_links(body))
+            .find(|link| link.contains("/auth/v1/verify"))
+    }
+
+    /// Returns the token hash carried by the verification link
+    ///
+    /// Templates using `{{ .TokenHash }}` put it in a `token_hash` parameter; the default
+    /// templates pass it as the `token` parameter of the action link.
+    pub fn token_hash(&self) -> Option<String> {
+        [&self.html, &self.text]
+            .into_iter()
+            .flat_map(|body| find_links(body))
+            .filter_map(|link| Url::parse(&link).ok())
+            .find_map(|url| {
+                let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+                let value = |key: &str| {
+                    params
+                        .iter()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, v)| v.clone())
+                };
+                value("token_hash").or_else(|| {
+                    url.path()
+                        .ends_with("/auth/v1/verify")
+                        .then(|| value("token"))
+                        .flatten()
+                })
+            })
+    }
+
+    /// Returns the verification type of the action link (e.g. `signup` or `recovery`)
+    pub fn verification_type(&self) -> Option<String> {
+        let link = Url::parse(&self.action_link()?).ok()?;
+        link.query_pairs()
+            .find(|(key, _)| key == "type")
+            .map(|(_, value)| value.into_owned())
+    }
+
+    /// Returns the first standalone 6 digit code in the plain text body, i.e. `{{ .Token }}`
+    pub fn otp(&self) -> Option<String> {
+        self.text
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|word| word.len() == 6)
+            .map(str::to_string)
+    }
+}
+
+impl MailCapture {
+    /// Creates a client for a Mailpit instance
+    pub fn mailpit(base_url: &str) -> Self {
+        Self::new(base_url, Backend::Mailpit)
+    }
+
+    /// Creates a client for an Inbucket instance
+    pub fn inbucket(base_url: &str) -> Self {
+        Self::new(base_url, Backend::Inbucket)
+    }
+
+    fn new(base_url: &str, backend: Backend) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            backend,
+        }
+    }
+
+    /// Returns the most recent email sent to `address`, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Http` if the mail catcher cannot be reached.
+    /// Returns `AuthError::Internal` if its response cannot be parsed.
+    #[instrument(skip(self))]
+    pub async fn latest_email(&self, address: &str) -> Result<Option<CapturedEmail>, AuthError> {
+        match self.backend {
+            Backend::Mailpit => self.latest_mailpit_email(address).await,
+            Backend::Inbucket => self.latest_inbucket_email(address).await,
+        }
+    }
+
+    /// Polls until an email for `address` arrives and returns the most recent one
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Timeout` if no email arrived within `options.timeout`.
+    /// Returns `AuthError::Http` or `AuthError::Internal` if polling the mail catcher fails.
+    #[instrument(skip(self))]
+    pub async fn wait_for_email(
+        &self,
+        address: &str,
+        options: PollOptions,
+    ) -> Result<CapturedEmail, AuthError> {
+        let deadline = tokio::time::Instant::now() + options.timeout;
+        loop {
+            if let Some(email) = self.latest_email(address).await? {
+                return Ok(email);
+            }
+            if tokio::time::Instant::now() + options.interval > deadline {
+                debug!("no email for {} before timeout", address);
+                return Err(AuthError::Timeout);
+            }
+            tokio::time::sleep(options.interval).await;
+        }
+    }
+
+    /// Deletes every caught email, so tests start from an empty inbox
+    ///
+    /// Inbucket only supports deleting per mailbox, so only the mailbox of `address` is
+    /// purged there.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Http` if the mail catcher cannot be reached.
+    #[instrument(skip(self))]
+    pub async fn clear(&self, address: &str) -> Result<(), AuthError> {
+        let url = match self.backend {
+            Backend::Mailpit => format!("{}/api/v1/messages", self.base_url),
+            Backend::Inbucket => {
+                format!("{}/api/v1/mailbox/{}", self.base_url, mailbox(address))
+            }
+        };
+        let resp = match self.http_client.delete(url).send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        if !resp.status().is_success() {
+            error!("clearing emails failed: {}", resp.status());
+            return Err(AuthError::Http);
+        }
+        Ok(())
+    }
+
+    async fn latest_mailpit_email(
+        &self,
+        address: &str,
+    ) -> Result<Option<CapturedEmail>, AuthError> {
+        #[derive(Deserialize)]
+        struct Search {
+            messages: Vec<Summary>,
+        }
+        #[derive(Deserialize)]
+        struct Summary {
+            #[serde(rename = "ID")]
+            id: String,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Message {
+            subject: String,
+            text: String,
+            #[serde(rename = "HTML")]
+            html: String,
+        }
+
+        // Search results are ordered newest first
+        let search: Search = self
+            .get_json(
+                &format!("{}/api/v1/search", self.base_url),
+                &[
+                    ("query", format!("to:\"{}\"", address)),
+                    ("limit", "1".to_string()),
+                ],
+            )
+            .await?;
+        let Some(summary) = search.messages.first() else {
+            return Ok(None);
+        };
+
+        let message: Message = self
+            .get_json(
+                &format!("{}/api/v1/message/{}", self.base_url, summary.id),
+                &[],
+            )
+            .await?;
+        Ok(Some(CapturedEmail {
+            subject: message.subject,
+            text: message.text,
+            html: message.html,
+        }))
+    }
+
+    async fn latest_inbucket_email(
+        &self,
+        address: &str,
+    ) -> Result<Option<CapturedEmail>, AuthError> {
+        #[derive(Deserialize)]
+        struct Summary {
+            id: String,
+        }
+        #[derive(Deserialize)]
+        struct Message {
+            subject: String,
+            body: Body,
+        }
+        #[derive(Deserialize)]
+        struct Body {
+            text: String,
+            html: String,
+        }
+
+        // Mailbox listings are ordered oldest first
+        let mailbox_url = format!("{}/api/v1/mailbox/{}", self.base_url, mailbox(address));
+        let summaries: Vec<Summary> = self.get_json(&mailbox_url, &[]).await?;
+        let Some(summary) = summaries.last() else {
+            return Ok(None);
+        };
+
+        let message: Message = self
+            .get_json(&format!("{}/{}", mailbox_url, summary.id), &[])
+            .await?;
+        Ok(Some(CapturedEmail {
+            subject: message.subject,
+            text: message.body.text,
+            html: message.body.html,
+        }))
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, AuthError> {
+        let resp = match self.http_client.get(url).query(query).send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        if !resp.status().is_success() {
+            error!("mail catcher responded with {}", resp.status());
+            return Err(AuthError::Http);
+        }
+        match resp.json::<T>().await {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+}
+
+/// Inbucket names mailboxes after the local part of the address
+fn mailbox(address: &str) -> &str {
+    address.split('@').next().unwrap_or(address)
+}
+
+/// Returns the `http(s)` links in an email body, with HTML entities in them decoded
+fn find_links(body: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("http") {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | ')'))
+            .unwrap_or(candidate.len());
+        let link = &candidate[..end];
+        if link.starts_with("http://") || link.starts_with("https://") {
+            links.push(link.replace("&amp;", "&"));
+        }
+        rest = &candidate[end.max(4)..];
+    }
+    links
+}
+
+/// Length of a time step, as used by GoTrue
+const TOTP_PERIOD_SECS: u64 = 30;
+/// Number of digits of a code, as used by GoTrue
+const TOTP_DIGITS: u32 = 6;
+
+/// Generator for the codes of a TOTP factor (RFC 6238, SHA-1, 6 digits, 30 second steps)
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::test_util::Totp;
+///
+/// // The `totp.secret` returned when enrolling the factor
+/// let totp = Totp::from_secret("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ")?;
+/// assert_eq!(totp.code_at(59), "287082");
+///
+/// let code = totp.current_code();
+/// # Ok::<(), supabase_auth_redux::AuthError>(())
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Totp {
+    /// Decoded shared secret
+    secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for Totp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Totp")
+    }
+}
+
+impl Totp {
+    /// Creates a generator from a base32 encoded secret
+    ///
+    /// Whitespace, padding and lowercase letters are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the secret is not valid base32.
+    pub fn from_secret(secret: &str) -> Result<Self, AuthError> {
+        let secret = decode_base32(secret).ok_or_else(|| {
+            error!("totp secret is not valid base32");
+            AuthError::InvalidParameters(None)
+        })?;
+        Ok(Self { secret })
+    }
+
+    /// Creates a generator from an `otpauth://totp/...` URI
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the URI has no valid `secret` parameter.
+    pub fn from_uri(uri: &str) -> Result<Self, AuthError> {
+        let uri = Url::parse(uri).map_err(|e| {
+            error!("{}", e);
+            AuthError::InvalidParameters(None)
+        })?;
+        let secret = uri
+            .query_pairs()
+            .find(|(key, _)| key == "secret")
+            .map(|(_, value)| value.into_owned())
+            .ok_or_else(|| {
+                error!("otpauth uri has no secret");
+                AuthError::InvalidParameters(None)
+            })?;
+        Self::from_secret(&secret)
+    }
+
+    /// Returns the code valid at `unix_time` (seconds since the epoch)
+    pub fn code_at(&self, unix_time: u64) -> String {
+        let counter = unix_time / TOTP_PERIOD_SECS;
+        let mut mac =
+            Hmac::<Sha1>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(&counter.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Dynamic truncation as specified by RFC 4226
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        format!(
+            "{:0width$}",
+            binary % 10u32.pow(TOTP_DIGITS),
+            width = TOTP_DIGITS as usize
+        )
+    }
+
+    /// Returns the code valid now
+    pub fn current_code(&self) -> String {
+        let now = time::OffsetDateTime::now_utc().unix_timestamp().max(0) as u64;
+        self.code_at(now)
+    }
+}
+
+/// Decodes RFC 4648 base32, ignoring whitespace, padding and case
+fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    let mut bytes = Vec::new();
+    let mut buffer: u64 = 0;
+    let mut bits = 0;
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase() as u8)?;
+        buffer = (buffer << 5) | value as u64;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    (!bytes.is_empty()).then_some(bytes)
+}
@@ -0,0 +1,101 @@
+//! tonic integration
+//!
+//! [`SupabaseAuthInterceptor`] reads the bearer token of a gRPC request's `authorization`
+//! metadata, verifies it locally with the client's [`JwtVerifier`](crate::jwt::JwtVerifier)
+//! and stores the token's [`Claims`](crate::models::claims::Claims) in the request
+//! extensions. Requests without a valid token are rejected with `UNAUTHENTICATED`.
+//!
+//! Interceptors run synchronously, so they cannot ask the auth server. To validate tokens
+//! with [`AuthClient::get_user_by_token`] instead, add
+//! [`SupabaseAuthLayer::server`](crate::tower::SupabaseAuthLayer::server) to the server's
+//! tower layers; handlers then find an [`AuthenticatedUser`](crate::tower::AuthenticatedUser)
+//! in the request extensions.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use supabase_auth_redux::models::claims::Claims;
+//! use supabase_auth_redux::tonic::SupabaseAuthInterceptor;
+//! use supabase_auth_redux::AuthClient;
+//!
+//! fn user_id(request: &::tonic::Request<()>) -> Option<uuid::Uuid> {
+//!     request.extensions().get::<Claims>().map(|claims| claims.sub)
+//! }
+//!
+//! # fn example(client: AuthClient) {
+//! let interceptor = SupabaseAuthInterceptor::new(client);
+//! // MyServiceServer::with_interceptor(MyService::default(), interceptor)
+//! # }
+//! ```
+
+use ::tonic::metadata::MetadataMap;
+use ::tonic::service::Interceptor;
+use ::tonic::{Request, Status};
+use tracing::{debug, error};
+
+use crate::tower::bearer_token;
+use crate::{AuthClient, AuthError};
+
+/// Interceptor verifying bearer tokens of gRPC requests
+///
+/// Requires a [`JwtVerifier`](crate::jwt::JwtVerifier) configured on the client.
+#[derive(Debug, Clone)]
+pub struct SupabaseAuthInterceptor {
+    client: AuthClient,
+    optional: bool,
+}
+
+impl SupabaseAuthInterceptor {
+    /// Creates an interceptor rejecting requests without a valid bearer token
+    pub fn new(client: AuthClient) -> Self {
+        Self {
+            client,
+            optional: false,
+        }
+    }
+
+    /// Lets requests without an `authorization` header through without claims
+    ///
+    /// Requests presenting an invalid token are still rejected.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+}
+
+impl Interceptor for SupabaseAuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let headers = std::mem::take(request.metadata_mut()).into_headers();
+        let token = bearer_token(&headers);
+        *request.metadata_mut() = MetadataMap::from_headers(headers);
+        let Some(token) = token else {
+            if self.optional {
+                return Ok(request);
+            }
+            return Err(Status::unauthenticated("missing bearer token"));
+        };
+
+        match self.client.verify_access_token(&token) {
+            Ok(claims) => {
+                request.extensions_mut().insert(claims);
+                Ok(request)
+            }
+            Err(e) => Err(status(e)),
+        }
+    }
+}
+
+/// Maps a token verification error to a gRPC status
+fn status(error: AuthError) -> Status {
+    match error {
+        // Raised when no verifier is configured
+        AuthError::InvalidParameters(None) => {
+            error!("cannot verify bearer token: {}", error);
+            Status::internal("bearer token verification unavailable")
+        }
+        error => {
+            debug!("rejected bearer token: {}", error);
+            Status::unauthenticated(error.to_string())
+        }
+    }
+}
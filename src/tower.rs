@@ -0,0 +1,166 @@
+//! tower integration
+//!
+//! [`SupabaseAuthLayer`] authenticates [`http::Request`]s carrying an
+//! `Authorization: Bearer` header and stores the outcome in the request extensions, so it
+//! works with any tower based stack (hyper, tonic, warp, axum). Authenticated requests
+//! carry the token's [`Claims`](crate::models::claims::Claims) or an [`AuthenticatedUser`], depending on the
+//! [`TokenValidation`]; requests presenting a rejected token carry an [`AuthFailure`].
+//! Requests are passed on either way, so services decide which routes require
+//! authentication.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::convert::Infallible;
+//!
+//! use supabase_auth_redux::models::claims::Claims;
+//! use supabase_auth_redux::tower::SupabaseAuthLayer;
+//! use supabase_auth_redux::AuthClient;
+//! use tower::{service_fn, Layer};
+//!
+//! # fn example(client: AuthClient) {
+//! let service = SupabaseAuthLayer::local(client).layer(service_fn(
+//!     |request: http::Request<()>| async move {
+//!         let user_id = request.extensions().get::<Claims>().map(|claims| claims.sub);
+//!         Ok::<_, Infallible>(format!("{:?}", user_id))
+//!     },
+//! ));
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{header, HeaderMap, Request};
+use tower_layer::Layer;
+use tower_service::Service;
+use tracing::debug;
+
+use crate::{AuthClient, AuthError, User};
+
+/// How [`SupabaseAuthLayer`] validates bearer tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenValidation {
+    /// Verify the token signature locally with the client's
+    /// [`JwtVerifier`](crate::jwt::JwtVerifier), providing
+    /// [`Claims`](crate::models::claims::Claims)
+    Local,
+    /// Look the user up with [`AuthClient::get_user_by_token`], providing
+    /// [`AuthenticatedUser`]; signed out sessions are rejected immediately
+    Server,
+}
+
+/// The user owning the request's bearer token
+///
+/// Stored in the request extensions by a [`SupabaseAuthLayer`] using
+/// [`TokenValidation::Server`].
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub User);
+
+/// The error a request's bearer token was rejected with, stored in its extensions
+#[derive(Debug, Clone)]
+pub struct AuthFailure(pub AuthError);
+
+/// Layer authenticating requests with an [`AuthClient`]
+#[derive(Debug, Clone)]
+pub struct SupabaseAuthLayer {
+    client: AuthClient,
+    validation: TokenValidation,
+}
+
+impl SupabaseAuthLayer {
+    /// Creates a layer validating tokens with the given strategy
+    pub fn new(client: AuthClient, validation: TokenValidation) -> Self {
+        Self { client, validation }
+    }
+
+    /// Creates a layer verifying tokens locally, see [`TokenValidation::Local`]
+    pub fn local(client: AuthClient) -> Self {
+        Self::new(client, TokenValidation::Local)
+    }
+
+    /// Creates a layer validating tokens with the auth server, see
+    /// [`TokenValidation::Server`]
+    pub fn server(client: AuthClient) -> Self {
+        Self::new(client, TokenValidation::Server)
+    }
+}
+
+impl<S> Layer<S> for SupabaseAuthLayer {
+    type Service = SupabaseAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SupabaseAuthService {
+            inner,
+            client: self.client.clone(),
+            validation: self.validation,
+        }
+    }
+}
+
+/// Service created by [`SupabaseAuthLayer`]
+#[derive(Debug, Clone)]
+pub struct SupabaseAuthService<S> {
+    inner: S,
+    client: AuthClient,
+    validation: TokenValidation,
+}
+
+impl<S, B> Service<Request<B>> for SupabaseAuthService<S>
+where
+    S: Service<Request<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        // Use the service that was polled ready and leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let client = self.client.clone();
+        let validation = self.validation;
+
+        Box::pin(async move {
+            if let Some(token) = bearer_token(request.headers()) {
+                let extensions = request.extensions_mut();
+                match validation {
+                    TokenValidation::Local => match client.verify_access_token(&token) {
+                        Ok(claims) => {
+                            extensions.insert(claims);
+                        }
+                        Err(e) => {
+                            debug!("rejected bearer token: {}", e);
+                            extensions.insert(AuthFailure(e));
+                        }
+                    },
+                    TokenValidation::Server => match client.get_user_by_token(&token).await {
+                        Ok(user) => {
+                            extensions.insert(AuthenticatedUser(user));
+                        }
+                        Err(e) => {
+                            debug!("rejected bearer token: {}", e);
+                            extensions.insert(AuthFailure(e));
+                        }
+                    },
+                }
+            }
+            inner.call(request).await
+        })
+    }
+}
+
+/// Returns the token of an `Authorization: Bearer` header
+pub(crate) fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then(|| token.to_string())
+}
@@ -0,0 +1,179 @@
+use tracing::{debug, error, info, instrument, trace_span, Instrument};
+
+use crate::error::AuthError;
+use crate::models::user::{UserSchema, UserUpdateParams};
+use crate::util::{error_with_body, handle_response};
+use crate::{AuthClient, IdType};
+
+impl AuthClient {
+    /// Updates the user owning an access token
+    ///
+    /// Changes the email address, phone number, password or user metadata through the
+    /// authenticated `/user` endpoint. New email addresses and phone numbers are confirmed
+    /// before they take effect, so the returned user lists them as `new_email` and
+    /// `new_phone` until then.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the user
+    /// * `params` - The attributes to change
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token is empty or the server rejects
+    /// the changes.
+    /// Returns `AuthError::UserAlreadyExists` if the new email address or phone number is
+    /// used by another user.
+    /// Returns `AuthError::WeakPassword` if the new password does not satisfy the
+    /// configured password policy or the project's password requirements.
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// # use supabase_auth_redux::models::user::UserUpdateParams;
+    /// # async fn example(client: AuthClient, access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = client
+    ///     .update_user(
+    ///         access_token,
+    ///         UserUpdateParams::new()
+    ///             .email("new@example.com")
+    ///             .data("full_name", "Jane Doe"),
+    ///     )
+    ///     .await?;
+    /// println!("Confirm {:?} to finish the change", user.new_email);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn update_user(
+        &self,
+        access_token: &str,
+        mut params: UserUpdateParams,
+    ) -> Result<UserSchema, AuthError> {
+        self.observe("update_user", None, async {
+            if access_token.is_empty() {
+                error!("empty token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+            if let Some(password) = &params.password {
+                self.check_password_policy(password)?;
+            }
+            params.email = params.email.map(|email| self.normalize_email(email));
+
+            let resp = match self
+                .send_with_failover(|api_url| {
+                    let mut request = self
+                        .http_client
+                        .put(format!("{}/user", api_url))
+                        .bearer_auth(access_token)
+                        .header("apiKey", &self.supabase_anon_key)
+                        .json(&params);
+                    if let Some(redirect_to) =
+                        self.redirect_url(params.email_redirect_to.as_deref())
+                    {
+                        request = request.query(&[("redirect_to", redirect_to)]);
+                    }
+                    request
+                })
+                .instrument(trace_span!("gotrue update user"))
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+
+            let resp_code_result = handle_response(&resp).await;
+            let resp_text = match resp.text().await {
+                Ok(resp_text) => resp_text,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Http);
+                }
+            };
+            debug!("resp_text: {}", self.redaction.response(&resp_text));
+            resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+            let user = match serde_json::from_str::<UserSchema>(&resp_text) {
+                Ok(user) => user,
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(AuthError::Internal);
+                }
+            };
+            info!(user_id = user.id.to_string(), "updated user");
+
+            Ok(user)
+        })
+        .await
+    }
+
+    /// Attaches credentials to an anonymous user, making it a permanent user
+    ///
+    /// The user keeps its ID, metadata and data, and can sign in with the email address
+    /// or phone number and password from then on. An email address is confirmed before it
+    /// takes effect, like any other email change.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - A valid JWT access token of the anonymous user
+    /// * `id` - The email address or phone number to attach
+    /// * `password` - The password to attach
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token or identifier is empty.
+    /// Returns `AuthError::UserAlreadyExists` if the identifier is used by another user.
+    /// Returns `AuthError::WeakPassword` if the password does not satisfy the configured
+    /// password policy.
+    /// Returns `AuthError::NotAuthorized` if the token is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// # async fn example(client: AuthClient, guest_access_token: &str) -> Result<(), supabase_auth_redux::AuthError> {
+    /// let user = client
+    ///     .convert_anonymous_user(
+    ///         guest_access_token,
+    ///         IdType::Email("guest@example.com".to_string()),
+    ///         "a-strong-password",
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_anonymous_user(
+        &self,
+        access_token: &str,
+        id: IdType,
+        password: &str,
+    ) -> Result<UserSchema, AuthError> {
+        let params = match id {
+            IdType::Email(email) if !email.is_empty() => UserUpdateParams::new().email(&email),
+            IdType::PhoneNumber(phone) if !phone.is_empty() => {
+                UserUpdateParams::new().phone(&phone)
+            }
+            _ => {
+                error!("empty identifier");
+                return Err(AuthError::InvalidParameters(None));
+            }
+        };
+        self.update_user(access_token, params.password(password))
+            .await
+    }
+}
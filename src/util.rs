@@ -1,21 +1,386 @@
-use crate::AuthError;
-use reqwest::StatusCode;
-use tracing::{debug, info, instrument};
+use std::time::Duration;
+
+use crate::error::ApiError;
+use crate::models::raw::Raw;
+use crate::{AuthClient, AuthError};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use time::format_description::well_known::Rfc2822;
+use tracing::{debug, error, info, instrument, trace_span, Instrument, Span};
+
+/// `gotrue_meta_security` field of requests protected by captcha
+#[derive(Debug, Serialize)]
+pub(crate) struct MetaSecurity<'a> {
+    pub(crate) captcha_token: &'a str,
+}
+
+impl<'a> MetaSecurity<'a> {
+    pub(crate) fn from_token(captcha_token: Option<&'a str>) -> Option<Self> {
+        captcha_token.map(|captcha_token| MetaSecurity { captcha_token })
+    }
+}
+
+/// Credentials a request to the auth API is sent with
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Credentials<'a> {
+    /// The anon key, for public endpoints
+    Anon,
+    /// A user's access token, with the anon key as API key
+    User(&'a str),
+    /// The service role key, for admin endpoints
+    ServiceRole,
+}
+
+/// A request to a GoTrue endpoint, sent with [`AuthClient::execute`]
+pub(crate) struct Endpoint<'a, Req: ?Sized = ()> {
+    method: Method,
+    /// Path below the auth base URL, e.g. `admin/users`
+    path: String,
+    credentials: Credentials<'a>,
+    query: Vec<(&'a str, String)>,
+    body: Option<&'a Req>,
+    retry: bool,
+    not_found: bool,
+}
+
+impl<'a> Endpoint<'a> {
+    pub(crate) fn new(
+        method: Method,
+        path: impl Into<String>,
+        credentials: Credentials<'a>,
+    ) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            credentials,
+            query: Vec::new(),
+            body: None,
+            retry: false,
+            not_found: false,
+        }
+    }
+}
+
+impl<'a, Req: Serialize + ?Sized> Endpoint<'a, Req> {
+    /// Sends `body` as the JSON request body
+    pub(crate) fn json<B: Serialize + ?Sized>(self, body: &'a B) -> Endpoint<'a, B> {
+        Endpoint {
+            method: self.method,
+            path: self.path,
+            credentials: self.credentials,
+            query: self.query,
+            body: Some(body),
+            retry: self.retry,
+            not_found: self.not_found,
+        }
+    }
+
+    /// Adds a query parameter
+    pub(crate) fn query(mut self, key: &'a str, value: impl Into<String>) -> Self {
+        self.query.push((key, value.into()));
+        self
+    }
+
+    /// Adds a query parameter if `value` is set
+    pub(crate) fn query_opt(self, key: &'a str, value: Option<&str>) -> Self {
+        match value {
+            Some(value) => self.query(key, value),
+            None => self,
+        }
+    }
+
+    /// Retries transient failures per the client's retry policy; only for idempotent
+    /// requests
+    pub(crate) fn retry(mut self) -> Self {
+        self.retry = true;
+        self
+    }
+
+    /// Maps a 404 response to `AuthError::NotFound(None)`, for endpoints addressing a
+    /// resource by ID
+    pub(crate) fn not_found(mut self) -> Self {
+        self.not_found = true;
+        self
+    }
+}
+
+impl AuthClient {
+    /// Sends a request to a GoTrue endpoint and returns the body of its success response
+    ///
+    /// Applies the credentials' `Authorization` and `apiKey` headers, fails over (and
+    /// retries, if the endpoint allows it) like every request, maps error statuses with
+    /// [`handle_response`] and attaches the error body with [`error_with_body`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::ServiceRoleKeyRequired` if the endpoint requires a service role
+    /// key and none is configured.
+    /// Returns `AuthError::Http` if the request fails or the body cannot be read.
+    /// Otherwise returns the error the response status and body map to.
+    pub(crate) async fn execute_text<Req: Serialize + ?Sized>(
+        &self,
+        endpoint: Endpoint<'_, Req>,
+    ) -> Result<String, AuthError> {
+        let (bearer, api_key) = match endpoint.credentials {
+            Credentials::Anon => (&*self.supabase_anon_key, &*self.supabase_anon_key),
+            Credentials::User(access_token) => (access_token, &*self.supabase_anon_key),
+            Credentials::ServiceRole => {
+                let service_role_key = self
+                    .supabase_service_role_key
+                    .as_deref()
+                    .ok_or(AuthError::ServiceRoleKeyRequired)?;
+                (service_role_key, service_role_key)
+            }
+        };
+
+        let request = |api_url: &str| {
+            let mut request = self
+                .http_client
+                .request(
+                    endpoint.method.clone(),
+                    format!("{}/{}", api_url, endpoint.path),
+                )
+                .bearer_auth(bearer)
+                .header("apiKey", api_key);
+            if !endpoint.query.is_empty() {
+                request = request.query(&endpoint.query);
+            }
+            if let Some(body) = endpoint.body {
+                request = request.json(body);
+            }
+            request
+        };
+        let sent = async {
+            if endpoint.retry {
+                self.send_with_retry(request).await
+            } else {
+                self.send_with_failover(request).await
+            }
+        };
+        let resp = match sent
+            .instrument(trace_span!(
+                "gotrue request",
+                method = %endpoint.method,
+                path = %endpoint.path
+            ))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+
+        let mut resp_code_result = handle_response(&resp).await;
+        if endpoint.not_found && resp.status() == StatusCode::NOT_FOUND {
+            resp_code_result = Err(AuthError::NotFound(None));
+        }
+        let resp_text = match resp.text().await {
+            Ok(resp_text) => resp_text,
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthError::Http);
+            }
+        };
+        debug!("resp_text: {}", self.redaction.response(&resp_text));
+        resp_code_result.map_err(|e| error_with_body(e, &resp_text))?;
+
+        Ok(resp_text)
+    }
+
+    /// Sends a request like [`AuthClient::execute_text`] and decodes the JSON body of its
+    /// success response
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Internal` if the body is not a `Resp`.
+    /// Otherwise fails like [`AuthClient::execute_text`].
+    pub(crate) async fn execute<Req: Serialize + ?Sized, Resp: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint<'_, Req>,
+    ) -> Result<Resp, AuthError> {
+        let resp_text = self.execute_text(endpoint).await?;
+        match serde_json::from_str::<Resp>(&resp_text) {
+            Ok(resp) => Ok(resp),
+            Err(e) => {
+                error!("{}", e);
+                Err(AuthError::Internal)
+            }
+        }
+    }
+}
 
 #[instrument]
 pub(super) async fn handle_response_code(resp_status: StatusCode) -> Result<(), AuthError> {
     info!(response.status = resp_status.as_u16());
     if !resp_status.is_success() {
         debug!("non-success response status code from supabase auth");
+        let api_error = Some(Box::new(ApiError::new(resp_status.as_u16())));
         return match resp_status {
-            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(AuthError::NotAuthorized),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(AuthError::NotAuthorized(api_error))
+            }
             StatusCode::UNPROCESSABLE_ENTITY | StatusCode::BAD_REQUEST => {
-                Err(AuthError::InvalidParameters)
+                Err(AuthError::InvalidParameters(api_error))
             }
-            StatusCode::NOT_ACCEPTABLE => Err(AuthError::NotFound),
-            StatusCode::INTERNAL_SERVER_ERROR => Err(AuthError::GeneralError),
-            _ => Err(AuthError::GeneralError),
+            StatusCode::NOT_ACCEPTABLE => Err(AuthError::NotFound(api_error)),
+            StatusCode::TOO_MANY_REQUESTS => Err(AuthError::RateLimited {
+                retry_after: None,
+                api_error,
+            }),
+            StatusCode::INTERNAL_SERVER_ERROR => Err(AuthError::GeneralError(api_error)),
+            _ => Err(AuthError::GeneralError(api_error)),
         };
     }
     Ok(())
 }
+
+/// Maps the status code of a response like [`handle_response_code`], adding the
+/// `Retry-After` delay to rate limit errors
+///
+/// Also records the request ID of the response on the current span and attaches it and
+/// the gateway headers to errors.
+pub(super) async fn handle_response(resp: &Response) -> Result<(), AuthError> {
+    let request_id = request_id(resp.headers());
+    if let Some(request_id) = &request_id {
+        Span::current().record("request_id", request_id.as_str());
+    }
+    handle_response_code(resp.status()).await.map_err(|mut e| {
+        if let AuthError::NotAuthorized(Some(api_error))
+        | AuthError::InvalidParameters(Some(api_error))
+        | AuthError::NotFound(Some(api_error))
+        | AuthError::RateLimited {
+            api_error: Some(api_error),
+            ..
+        }
+        | AuthError::GeneralError(Some(api_error)) = &mut e
+        {
+            api_error.request_id = request_id;
+            api_error.gateway_headers = gateway_headers(resp.headers());
+        }
+        match e {
+            AuthError::RateLimited { api_error, .. } => AuthError::RateLimited {
+                retry_after: retry_after(resp.headers()),
+                api_error,
+            },
+            e => e,
+        }
+    })
+}
+
+/// Returns the request ID of a response, from `x-request-id` or, as set by the Supabase
+/// gateway, `sb-request-id`
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    ["x-request-id", "sb-request-id"]
+        .into_iter()
+        .filter_map(|name| headers.get(name)?.to_str().ok())
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the `sb-gateway-*` headers of a response
+fn gateway_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| name.as_str().starts_with("sb-gateway-"))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Parses a `Retry-After` header given in seconds or as an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = time::OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let delay = date - time::OffsetDateTime::now_utc();
+    Some(delay.try_into().unwrap_or(Duration::ZERO))
+}
+
+/// Attaches an error response body to an error returned by [`handle_response_code`]
+///
+/// Also records the GoTrue error code of the body on the current span.
+pub(super) fn error_with_body(e: AuthError, resp_text: &str) -> AuthError {
+    record_error_code(resp_text);
+    e.with_response_body(resp_text)
+}
+
+/// Records the GoTrue error code of an error response body on the current span
+///
+/// Newer GoTrue versions send `error_code`, older ones an OAuth style `error`.
+fn record_error_code(resp_text: &str) {
+    let Ok(body) = serde_json::from_str::<serde_json::Value>(resp_text) else {
+        return;
+    };
+    if let Some(code) = body
+        .get("error_code")
+        .or_else(|| body.get("error"))
+        .and_then(|code| code.as_str())
+    {
+        Span::current().record("gotrue_error_code", code);
+    }
+}
+
+/// Parses a response body into both its typed and its raw JSON representation
+pub(super) fn parse_with_raw<T: DeserializeOwned>(resp_text: &str) -> Result<Raw<T>, AuthError> {
+    let raw = match serde_json::from_str::<serde_json::Value>(resp_text) {
+        Ok(raw) => raw,
+        Err(e) => {
+            error!("{}", e);
+            return Err(AuthError::Internal);
+        }
+    };
+    let value = match serde_json::from_value::<T>(raw.clone()) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("{}", e);
+            return Err(AuthError::Internal);
+        }
+    };
+    Ok(Raw { value, raw })
+}
+
+/// Masks an email address or phone number for logging (`t***@example.com`, `+1415•••0100`)
+pub(crate) fn mask_identifier(value: &str) -> String {
+    if let Some((local, domain)) = value.split_once('@') {
+        let first = local.chars().next().map(String::from).unwrap_or_default();
+        return format!("{}***@{}", first, domain);
+    }
+
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() >= 10 {
+        let head: String = chars[..5].iter().collect();
+        let tail: String = chars[chars.len() - 4..].iter().collect();
+        return format!("{}•••{}", head, tail);
+    }
+    let tail: String = chars[chars.len().saturating_sub(2)..].iter().collect();
+    format!("•••{}", tail)
+}
+
+/// Masks a token for logging, keeping its first and last 4 characters (`eyJh•••c2ln`)
+///
+/// Tokens shorter than 16 characters are masked entirely, since keeping 8 of their
+/// characters would reveal most of them.
+///
+/// # Example
+///
+/// ```rust
+/// use supabase_auth_redux::mask_token;
+///
+/// assert_eq!(mask_token("eyJhbGciOiJIUzI1NiJ9.e30.c2lnbmF0dXJl"), "eyJh•••dXJl");
+/// assert_eq!(mask_token("short"), "•••");
+/// ```
+pub fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() < 16 {
+        return "•••".to_string();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}•••{}", head, tail)
+}
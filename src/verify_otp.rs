@@ -0,0 +1,176 @@
+use reqwest::Method;
+use serde::Serialize;
+use tracing::{error, info, instrument};
+
+use crate::error::AuthError;
+use crate::models::otp::OtpType;
+use crate::models::session::Session;
+use crate::models::token::TokenResponse;
+use crate::util::{Credentials, Endpoint};
+use crate::{AuthClient, IdType};
+
+#[derive(Debug, Serialize)]
+pub(crate) struct VerifyRequest<'a> {
+    #[serde(rename = "type")]
+    pub(crate) otp_type: OtpType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) token: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) token_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) phone: Option<&'a str>,
+}
+
+impl AuthClient {
+    /// Verifies a one-time code sent to an email address or phone number
+    ///
+    /// Completes the flows started by [`AuthClient::signin_with_otp`], signup confirmation,
+    /// password recovery and email changes, and signs the user in.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The email address or phone number the code was sent to
+    /// * `token` - The code from the email or text message
+    /// * `otp_type` - The flow that sent the code
+    ///
+    /// # Returns
+    ///
+    /// Returns a `TokenResponse` containing access and refresh tokens for the verified user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the email, phone number or code is empty.
+    /// Returns `AuthError::NotAuthorized` if the code is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, IdType};
+    /// # use supabase_auth_redux::models::otp::{OtpOptions, OtpType};
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let phone = IdType::PhoneNumber("+14155550100".to_string());
+    /// client.signin_with_otp(phone.clone(), OtpOptions::new()).await?;
+    ///
+    /// // The user enters the code from the text message
+    /// let tokens = client.verify_otp(phone, "123456", OtpType::Sms).await?;
+    /// println!("Access token: {}", tokens.access_token);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn verify_otp(
+        &self,
+        id: IdType,
+        token: &str,
+        otp_type: OtpType,
+    ) -> Result<TokenResponse, AuthError> {
+        self.observe("verify_otp", Some(id.to_string()), async {
+            if token.is_empty() {
+                error!("empty token");
+                return Err(AuthError::InvalidParameters(None));
+            }
+            let id = self.normalize_id(id);
+            let (email, phone) = match &id {
+                IdType::Email(email) => (Some(email.as_str()), None),
+                IdType::PhoneNumber(phone_number) => (None, Some(phone_number.as_str())),
+            };
+            if email.or(phone).is_none_or(str::is_empty) {
+                error!("empty email or phone_number");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let body = VerifyRequest {
+                otp_type,
+                token: Some(token),
+                token_hash: None,
+                email,
+                phone,
+            };
+            let token_response = self.verify_request(&body).await?;
+            info!(?otp_type, "otp verified");
+
+            Ok(token_response)
+        })
+        .await
+    }
+
+    /// Verifies the token hash of a confirmation link and signs the user in
+    ///
+    /// Server-rendered apps whose email templates link to their own endpoint (e.g.
+    /// `/auth/confirm?token_hash=...&type=signup`) call this from that endpoint to complete
+    /// signup confirmation, magic link, recovery, invite and email change flows.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_hash` - The `token_hash` query parameter of the link
+    /// * `otp_type` - The `type` query parameter of the link
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::InvalidParameters` if the token hash is empty.
+    /// Returns `AuthError::NotAuthorized` if the token hash is invalid or expired.
+    /// Returns `AuthError::Http` if the API request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::AuthClient;
+    /// use supabase_auth_redux::models::otp::OtpType;
+    ///
+    /// # async fn example(client: AuthClient) -> Result<(), supabase_auth_redux::AuthError> {
+    /// // Handling GET /auth/confirm?token_hash=abc123&type=recovery
+    /// let session = client.verify_token_hash("abc123", OtpType::Recovery).await?;
+    /// println!("Signed in until {}", session.expires_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context, err, latency_ms, gotrue_error_code, request_id))]
+    pub async fn verify_token_hash(
+        &self,
+        token_hash: &str,
+        otp_type: OtpType,
+    ) -> Result<Session, AuthError> {
+        self.observe("verify_token_hash", None, async {
+            if token_hash.is_empty() {
+                error!("empty token hash");
+                return Err(AuthError::InvalidParameters(None));
+            }
+
+            let body = VerifyRequest {
+                otp_type,
+                token: None,
+                token_hash: Some(token_hash),
+                email: None,
+                phone: None,
+            };
+            let token_response = self.verify_request(&body).await?;
+            info!(?otp_type, "token hash verified");
+
+            Ok(Session::from(token_response))
+        })
+        .await
+    }
+
+    /// Sends a verification request and returns the tokens of the signed in user
+    pub(crate) async fn verify_request(
+        &self,
+        body: &VerifyRequest<'_>,
+    ) -> Result<TokenResponse, AuthError> {
+        self.execute(Endpoint::new(Method::POST, "verify", Credentials::Anon).json(body))
+            .await
+    }
+
+    /// Sends a verification request and returns the response body
+    pub(crate) async fn verify_request_text(
+        &self,
+        body: &VerifyRequest<'_>,
+    ) -> Result<String, AuthError> {
+        self.execute_text(Endpoint::new(Method::POST, "verify", Credentials::Anon).json(body))
+            .await
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tracing::{debug, info, instrument};
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::user::UserSchema;
+use crate::AuthClient;
+
+/// Polling configuration for [`AuthClient::wait_for_email_confirmation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollOptions {
+    /// Delay between two consecutive lookups of the user record
+    pub interval: Duration,
+    /// Maximum time to wait for the confirmation before giving up
+    pub timeout: Duration,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Identifies the user whose confirmation status is polled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationTarget {
+    /// Look the user up by ID (see [`AuthClient::get_user_by_id`])
+    UserId(Uuid),
+    /// Look the user up with one of their access tokens (see [`AuthClient::get_user_by_token`])
+    AccessToken(String),
+}
+
+impl From<Uuid> for ConfirmationTarget {
+    fn from(user_id: Uuid) -> Self {
+        ConfirmationTarget::UserId(user_id)
+    }
+}
+
+impl From<&str> for ConfirmationTarget {
+    fn from(access_token: &str) -> Self {
+        ConfirmationTarget::AccessToken(access_token.to_owned())
+    }
+}
+
+impl From<String> for ConfirmationTarget {
+    fn from(access_token: String) -> Self {
+        ConfirmationTarget::AccessToken(access_token)
+    }
+}
+
+impl AuthClient {
+    /// Waits until a user has confirmed their email address
+    ///
+    /// This method polls the user record every `options.interval` until `email_confirmed_at`
+    /// is set, which is useful for onboarding backends and integration tests that must block
+    /// until the confirmation link has been followed.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The user to watch, either a user ID or an access token
+    /// * `options` - Polling interval and overall timeout
+    ///
+    /// # Returns
+    ///
+    /// Returns the confirmed `UserSchema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AuthError::Timeout` if the email is still unconfirmed after `options.timeout`.
+    /// Returns `AuthError::NotFound` if no user exists for the given ID.
+    /// Returns any error produced by the underlying user lookup.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use supabase_auth_redux::{AuthClient, PollOptions};
+    /// # use std::time::Duration;
+    /// # use uuid::Uuid;
+    /// # async fn example() -> Result<(), supabase_auth_redux::AuthError> {
+    /// let client = AuthClient::new("https://your-project.supabase.co", "your-anon-key")?;
+    ///
+    /// let user_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+    /// let user = client
+    ///     .wait_for_email_confirmation(
+    ///         user_id,
+    ///         PollOptions {
+    ///             interval: Duration::from_secs(2),
+    ///             timeout: Duration::from_secs(120),
+    ///         },
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("Confirmed at: {:?}", user.email_confirmed_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip_all, fields(tenant_id = self.context.tenant_id.as_deref(), context = %self.context))]
+    pub async fn wait_for_email_confirmation(
+        &self,
+        target: impl Into<ConfirmationTarget>,
+        options: PollOptions,
+    ) -> Result<UserSchema, AuthError> {
+        let target = target.into();
+        let deadline = Instant::now() + options.timeout;
+
+        loop {
+            let user = match &target {
+                ConfirmationTarget::UserId(user_id) => self
+                    .get_user_by_id(*user_id)
+                    .await?
+                    .ok_or(AuthError::NotFound(None))?,
+                ConfirmationTarget::AccessToken(token) => self.get_user_by_token(token).await?,
+            };
+            if user.email_confirmed_at.is_some() {
+                info!(user_id = user.id.to_string(), "email confirmed");
+                return Ok(user);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                debug!("timed out waiting for email confirmation");
+                return Err(AuthError::Timeout);
+            }
+            debug!("email not confirmed yet");
+            tokio::time::sleep(options.interval.min(deadline - now)).await;
+        }
+    }
+}
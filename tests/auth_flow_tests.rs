@@ -112,7 +112,7 @@ async fn test_token_expiry_handling() {
 
     // Verify token has expiry information
     assert!(tokens.expires_in > 0);
-    assert!(tokens.expires_at > 0);
+    assert!(tokens.expires_at.is_some_and(|expires_at| expires_at > 0));
 
     // In a real test, we'd wait for token expiry, but that takes too long
     // Instead, we'll just verify the refresh mechanism works
@@ -126,3 +126,231 @@ async fn test_token_expiry_handling() {
 
     println!("✓ Token expiry handling test passed");
 }
+
+#[tokio::test]
+async fn test_wait_for_email_confirmation() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let client = config.create_client();
+
+    // Local Supabase auto-confirms emails, so the wait returns on the first poll
+    let test_user = TestUser::create(client.clone())
+        .await
+        .expect("Failed to create test user");
+
+    let user = client
+        .wait_for_email_confirmation(
+            test_user.access_token.as_str(),
+            supabase_auth_redux::PollOptions {
+                interval: std::time::Duration::from_millis(200),
+                timeout: std::time::Duration::from_secs(5),
+            },
+        )
+        .await
+        .expect("Email should be confirmed");
+
+    assert_eq!(user.id, test_user.id);
+    assert!(user.email_confirmed_at.is_some());
+
+    println!("✓ Wait for email confirmation test passed");
+}
+
+#[tokio::test]
+async fn test_admin_list_users_filtered() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let Some(admin_client) = config.create_admin_client() else {
+        eprintln!("Skipping test - SUPABASE_SERVICE_ROLE_KEY is not set");
+        return;
+    };
+
+    let test_user = TestUser::create(config.create_client())
+        .await
+        .expect("Failed to create test user");
+
+    let users = admin_client
+        .admin_list_users_filtered(
+            &supabase_auth_redux::models::admin::UserListFilter::new()
+                .search(test_user.email.clone())
+                .created_after(time::OffsetDateTime::now_utc() - time::Duration::hours(1)),
+        )
+        .await
+        .expect("Listing users should succeed");
+
+    assert!(users.iter().any(|user| user.id == test_user.id));
+
+    println!("✓ Admin list users filtered test passed");
+}
+
+#[tokio::test]
+async fn test_with_raw_variants() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let client = config.create_client();
+
+    let test_user = TestUser::create(client.clone())
+        .await
+        .expect("Failed to create test user");
+
+    let tokens = client
+        .signin_with_password_with_raw(
+            supabase_auth_redux::IdType::Email(test_user.email.clone()),
+            test_user.password.clone(),
+        )
+        .await
+        .expect("Signin should succeed");
+    assert_eq!(
+        tokens.raw["access_token"],
+        tokens.value.access_token.as_str()
+    );
+
+    let user = client
+        .get_user_by_token_with_raw(&tokens.value.access_token)
+        .await
+        .expect("Token validation should succeed");
+    assert_eq!(user.value.id, test_user.id);
+    assert_eq!(user.raw["id"], test_user.id.to_string());
+
+    println!("✓ With raw variants test passed");
+}
+
+#[tokio::test]
+async fn test_signin_with_otp_for_unknown_user() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let client = config.create_client();
+
+    let result = client
+        .signin_with_otp(
+            supabase_auth_redux::IdType::Email(format!(
+                "unknown-{}@example.com",
+                uuid::Uuid::new_v4()
+            )),
+            supabase_auth_redux::models::otp::OtpOptions::new().should_create_user(false),
+        )
+        .await;
+    assert!(
+        matches!(result, Err(supabase_auth_redux::AuthError::UserNotFound)),
+        "OTP sign-in should not create users, got {:?}",
+        result
+    );
+
+    println!("✓ OTP sign-in for unknown user test passed");
+}
+
+#[tokio::test]
+async fn test_email_normalization_prevents_duplicates() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let client = supabase_auth_redux::AuthClient::builder()
+        .api_url(&config.api_url)
+        .anon_key(&config.anon_key)
+        .email_normalization(supabase_auth_redux::email_normalization::EmailNormalization::new())
+        .build()
+        .expect("Failed to create auth client");
+
+    let email = format!("Test-{}@Example.com", uuid::Uuid::new_v4());
+    let (user, _) = client
+        .signup(
+            supabase_auth_redux::IdType::Email(format!("  {}  ", email)),
+            "TestPassword123!".to_string(),
+            None,
+        )
+        .await
+        .expect("Signup should succeed");
+    assert_eq!(user.email, Some(email.to_lowercase()));
+
+    let tokens = client
+        .signin_with_password(
+            supabase_auth_redux::IdType::Email(email.to_uppercase()),
+            "TestPassword123!".to_string(),
+        )
+        .await
+        .expect("Signin with differently cased email should succeed");
+    assert!(!tokens.access_token.is_empty());
+
+    if let Some(admin_client) = config.create_admin_client() {
+        let _ = admin_client.hard_delete_user(user.id).await;
+    }
+
+    println!("✓ Email normalization test passed");
+}
+
+#[cfg(feature = "local-dev")]
+#[tokio::test]
+async fn test_verify_access_token_against_local_stack() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let client = supabase_auth_redux::AuthClient::builder()
+        .api_url(&config.api_url)
+        .anon_key(&config.anon_key)
+        .jwt_verifier(
+            supabase_auth_redux::jwt::JwtVerifier::discover_local()
+                .expect("Local JWT secret should be discovered"),
+        )
+        .build()
+        .expect("Failed to create auth client");
+
+    let test_user = TestUser::create(client.clone())
+        .await
+        .expect("Failed to create test user");
+    let tokens = test_user.signin().await.expect("Signin should succeed");
+
+    let claims = client
+        .verify_access_token(&tokens.access_token)
+        .expect("Access token should verify locally");
+    assert_eq!(claims.sub, test_user.id);
+
+    println!("✓ Local access token verification test passed");
+}
+
+#[tokio::test]
+async fn test_admin_list_deleted_users() {
+    require_supabase!();
+    test_helper::init_test_env();
+
+    let config = TestConfig::from_env();
+    let Some(admin_client) = config.create_admin_client() else {
+        eprintln!("Skipping test - SUPABASE_SERVICE_ROLE_KEY is not set");
+        return;
+    };
+
+    let test_user = TestUser::create(config.create_client())
+        .await
+        .expect("Failed to create test user");
+    admin_client
+        .soft_delete_user(test_user.id)
+        .await
+        .expect("Soft delete should succeed");
+
+    let deleted_users = admin_client
+        .admin_list_deleted_users(None)
+        .await
+        .expect("Listing deleted users should succeed");
+    assert!(deleted_users.iter().any(|user| user.id == test_user.id));
+    assert!(deleted_users.iter().all(|user| user.deleted_at.is_some()));
+
+    let due_users = admin_client
+        .admin_list_deleted_users(Some(
+            time::OffsetDateTime::now_utc() - time::Duration::days(1),
+        ))
+        .await
+        .expect("Listing deleted users should succeed");
+    assert!(!due_users.iter().any(|user| user.id == test_user.id));
+
+    let _ = admin_client.hard_delete_user(test_user.id).await;
+
+    println!("✓ Admin list deleted users test passed");
+}